@@ -7,15 +7,20 @@ use std::{
 use firecore_world::{
     character::{npc::group::NpcGroup, Activity},
     map::{
-        data::{tile::PaletteDataMap, FieldItemData, FieldMoveData, WorldMapData, WorldNpcData},
-        wild::WildChances,
+        data::{
+            tile::PaletteDataMap, BadgeData, FieldItemData, FieldMoveData, ObjectData,
+            WorldMapData, WorldNpcData,
+        },
+        evolution::EvolutionData,
+        medicine::MedicineData,
+        wild::{EncounterData, WildChances},
         PaletteId,
     },
     positions::Spot,
     script::default::DefaultWorldScriptEngine,
     serialized::{
-        SerializedCharacterGroupTextures, SerializedPalette, SerializedPlayerTexture,
-        SerializedTextures,
+        SerializedCharacterGroupTextures, SerializedObjectTextures, SerializedPalette,
+        SerializedPlayerTexture, SerializedTextures,
     },
 };
 use hashbrown::HashMap;
@@ -34,33 +39,62 @@ pub fn build(root: impl AsRef<Path>, assets: &Path) {
 
         let data = firecore_world_gen::create_data().unwrap();
 
-        let firecore_world_gen::WorldData { maps, scripts } =
-            firecore_world_gen::compile(mappings, edits, data).unwrap();
+        let firecore_world_gen::WorldData {
+            maps,
+            scripts,
+            palettes: behaviours,
+            machines,
+        } = firecore_world_gen::compile(mappings, edits, data).unwrap();
 
         let (npc, npcs) = load_npc_groups(assets.join("world/npcs"), assets.join("world/trainers"));
 
+        let (objects, object_textures) = load_objects(assets.join("world/entities"));
+
         let BuilderWorldData {
-            palettes,
+            mut palettes,
             wild,
+            encounters,
             moves,
             items,
+            badges,
+            evolutions,
+            medicine,
             spawn,
         } = ron::from_str(&read_to_string(assets.join("world/data.ron")).unwrap()).unwrap();
 
+        for (id, behaviours) in behaviours {
+            let palette = palettes.entry(id).or_default();
+            palette.ice.extend(behaviours.ice);
+            palette.stops.extend(behaviours.stops);
+            palette.slopes.extend(behaviours.slopes);
+            for (direction, tiles) in behaviours.spinners {
+                palette.spinners.entry(direction).or_default().extend(tiles);
+            }
+            for (direction, tiles) in behaviours.currents {
+                palette.currents.entry(direction).or_default().extend(tiles);
+            }
+        }
+
         let world = WorldMapData {
             maps,
+            objects,
             palettes,
             npc,
             wild,
+            encounters,
             spawn,
             moves,
             items,
+            badges,
+            evolutions,
+            medicine,
+            machines,
         };
 
         let textures = SerializedTextures {
             palettes: palette(assets.join("world/textures/palettes")),
             npcs,
-            objects: Default::default(),
+            objects: object_textures,
             player: player(assets.join("world/textures/player")),
         };
 
@@ -74,8 +108,16 @@ pub fn build(root: impl AsRef<Path>, assets: &Path) {
 pub struct BuilderWorldData {
     pub palettes: PaletteDataMap,
     pub wild: WildChances,
+    #[serde(default)]
+    pub encounters: EncounterData,
     pub moves: FieldMoveData,
     pub items: FieldItemData,
+    #[serde(default)]
+    pub badges: BadgeData,
+    #[serde(default)]
+    pub evolutions: EvolutionData,
+    #[serde(default)]
+    pub medicine: MedicineData,
     pub spawn: Spot,
 }
 
@@ -283,6 +325,62 @@ pub fn load_npc_groups(
     )
 }
 
+pub fn load_objects(path: impl AsRef<Path>) -> (ObjectData, SerializedObjectTextures) {
+    let path = path.as_ref();
+    let mut objects = HashMap::new();
+    let mut textures = HashMap::new();
+
+    for path in read_dir(path)
+        .unwrap_or_else(|err| {
+            panic!(
+                "Could not get object directory at {:?} with error {}",
+                path, err
+            )
+        })
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+    {
+        let name = filename(&path);
+
+        let id = name.parse().unwrap_or_else(|err| {
+            panic!(
+                "Cannot parse object folder name {} into id with error {}",
+                name, err
+            )
+        });
+
+        let ron_path = path.join(format!("{}.ron", name));
+
+        let group = ron::from_str(&read_to_string(&ron_path).unwrap_or_else(|err| {
+            panic!(
+                "Could not read object file at {:?} with error {}",
+                ron_path, err
+            )
+        }))
+        .unwrap_or_else(|err| {
+            panic!(
+                "Could not decode object file at {:?} with error {}",
+                ron_path, err
+            )
+        });
+
+        let texture_path = path.join(format!("{}.png", name));
+
+        let texture = read(&texture_path).unwrap_or_else(|err| {
+            panic!(
+                "Could not read object texture at {:?} with error {}",
+                texture_path, err
+            )
+        });
+
+        objects.insert(id, group);
+        textures.insert(id, texture);
+    }
+
+    (objects, textures)
+}
+
 fn get_npc_type_file(path: &Path) -> PathBuf {
     for entry in read_dir(path).unwrap().flatten() {
         let path = entry.path();
@@ -22,9 +22,12 @@ use pokengine::{
     pokedex::{
         item::{usage::ItemExecution, Item, ItemCategory},
         moves::Move,
-        pokemon::Pokemon,
+        pokemon::{
+            owned::{OwnedPokemon, SavedPokemon},
+            Level, Pokemon, PokemonId,
+        },
         types::Effective,
-        Dex, Money,
+        Dex,
     },
     texture::{ItemTextures, PokemonTextures, TrainerGroupTextures},
     TrainerGroupId,
@@ -33,7 +36,7 @@ use pokengine::{
 use battle::{
     endpoint::{MpscClient, MpscEndpoint},
     message::{ClientMessage, ServerMessage},
-    moves::{damage::ClientDamage, ClientMove, ClientMoveAction, MoveCancel},
+    moves::{damage::ClientDamage, BattleMove, ClientMove, ClientMoveAction, MoveCancel},
     party::PlayerParty,
     pokemon::Indexed,
     prelude::{
@@ -64,11 +67,24 @@ use action::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleTrainer {
-    pub worth: Money,
     pub texture: TrainerGroupId,
     pub defeat: Vec<MessagePage<[f32; 4], ()>>,
 }
 
+/// Weather that is active when a battle starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleWeather {
+    Clear,
+    Rain,
+    Sun,
+}
+
+impl Default for BattleWeather {
+    fn default() -> Self {
+        Self::Clear
+    }
+}
+
 pub struct BattlePlayerGui<ID: Eq + Hash + Display + Clone> {
     state: BattlePlayerState<ID>,
     should_select: bool,
@@ -82,6 +98,21 @@ pub struct BattlePlayerGui<ID: Eq + Hash + Display + Clone> {
     local: Option<GuiLocalPlayer<ID>>,
     remotes: GuiRemotePlayers<ID>,
 
+    /// Pokemon caught this battle, waiting to be given to the player
+    caught: Vec<SavedPokemon>,
+    /// Opponent species revealed this battle
+    seen: Vec<PokemonId>,
+    /// Party members that were sent out this battle
+    participants: Vec<usize>,
+
+    /// Highest level each party member obeys the player at, if it can disobey
+    obedience: Vec<Option<Level>>,
+    /// Random roll for each active pokemon that disobeys this turn
+    disobeying: Vec<Option<u32>>,
+    /// Party members that ignored orders, waiting for their move to be shown
+    ignored: Vec<usize>,
+    weather: BattleWeather,
+
     client: MpscClient<ID, BattleTrainer>,
     endpoint: MpscEndpoint<ID, BattleTrainer>,
 }
@@ -115,6 +146,13 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
             should_end: None,
             local: None,
             remotes: Default::default(),
+            caught: Default::default(),
+            participants: Default::default(),
+            seen: Default::default(),
+            obedience: Default::default(),
+            disobeying: Default::default(),
+            ignored: Default::default(),
+            weather: Default::default(),
             client,
             endpoint,
         }
@@ -180,6 +218,14 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                 .collect(),
         };
 
+        self.seen.extend(
+            self.remotes
+                .players
+                .values()
+                .flat_map(|player| player.pokemon.iter().flatten())
+                .map(|pokemon| pokemon.pokemon.id),
+        );
+
         let player = battle::party::PlayerParty {
             name: client.local.name,
             id: client.local.id,
@@ -193,6 +239,8 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
             trainer: client.local.trainer,
         };
 
+        self.participants.extend(player.active.iter().flatten());
+
         let local = GuiLocalPlayer {
             player,
             selecting: None,
@@ -221,6 +269,23 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                             StartableAction::Selecting => {
                                 self.should_select = true;
                                 self.gui.panel.despawn();
+                                for index in local.player.active.iter().flatten() {
+                                    if !self.participants.contains(index) {
+                                        self.participants.push(*index);
+                                    }
+                                }
+                                let obedience = &self.obedience;
+                                self.disobeying = local
+                                    .player
+                                    .active
+                                    .iter()
+                                    .map(|active| {
+                                        let index = (*active)?;
+                                        let cap = (*obedience.get(index)?)?;
+                                        let level = local.player.pokemon.get(index)?.level;
+                                        Self::disobeys(random, level, cap).then(|| random.gen())
+                                    })
+                                    .collect();
                             }
                             StartableAction::Turns(queue) => {
                                 self.state = BattlePlayerState::Moving(MoveQueue {
@@ -263,6 +328,7 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                                 })
                                 .flatten()
                             {
+                                self.seen.push(unknown.pokemon.id);
                                 player.add(target.index(), Some(unknown));
                             } else {
                                 warn!("Could not initialize remote pokemon at {:?}", target);
@@ -286,6 +352,7 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                             }
                         },
                         ServerMessage::Catch(instance) => {
+                            self.caught.push(instance.clone());
                             match instance.init(random, pokedex, movedex, itemdex) {
                                 Some(instance) => {
                                     local.player.pokemon.push(instance);
@@ -385,6 +452,7 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                             true => {
                                 if self.opener.update(app, &mut self.gui, local, &self.remotes) {
                                     self.opener.reset();
+                                    self.gui.text.on_weather(self.weather);
                                     self.state = BattlePlayerState::WaitToSelect;
                                     // self.update(app, plugins, dex, pokedex, movedex, itemdex, delta);
                                 }
@@ -439,7 +507,26 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                                     let action = self.gui.actions.remove(0);
                                     match action {
                                         BattleAction::Action(action) => {
-                                            self.client.send(ClientMessage::Move(current, action));
+                                            let index =
+                                                local.player.active.get(current).copied().flatten();
+                                            let ignored = self
+                                                .disobeying
+                                                .get(current)
+                                                .copied()
+                                                .flatten()
+                                                .zip(index)
+                                                .and_then(|(roll, index)| {
+                                                    let pokemon =
+                                                        local.player.pokemon.get(index)?;
+                                                    let action =
+                                                        Self::disobey(pokemon, &action, roll)?;
+                                                    self.ignored.push(index);
+                                                    Some(action)
+                                                });
+                                            self.client.send(ClientMessage::Move(
+                                                current,
+                                                ignored.unwrap_or(action),
+                                            ));
                                         }
                                         BattleAction::Forfeit => {
                                             self.client.send(ClientMessage::Forfeit);
@@ -462,6 +549,17 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                                         };
                                     }
                                     Some(Indexed(user_id, action)) => {
+                                        let ignored = match self.ignored.iter().position(|i| {
+                                            user_id.team() == local.player.id()
+                                                && *i == user_id.index()
+                                        }) {
+                                            Some(i) => {
+                                                self.ignored.swap_remove(i);
+                                                true
+                                            }
+                                            None => false,
+                                        };
+
                                         let user = match user_id.team() == local.player.id() {
                                             true => {
                                                 Some(&mut local.player as &mut dyn PlayerView<ID>)
@@ -506,6 +604,12 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
                                                                                             pp,
                                                                                         );
 
+                                                                                    if ignored {
+                                                                                        self.gui.text.on_disobey(
+                                                                                            user_active.name(),
+                                                                                        );
+                                                                                    }
+
                                                                                     self.gui.text.on_move(
                                                                                         &pokemon_move,
                                                                                         user_active.name(),
@@ -997,6 +1101,85 @@ impl<ID: Clone + Debug + Display + Hash + Eq> BattlePlayerGui<ID> {
         self.remotes.players.clear();
         self.local = None;
         self.remotes.current = 0;
+        self.caught.clear();
+        self.seen.clear();
+        self.participants.clear();
+        self.obedience.clear();
+        self.disobeying.clear();
+        self.ignored.clear();
+        self.weather = Default::default();
+    }
+
+    pub fn weather(&self) -> BattleWeather {
+        self.weather
+    }
+
+    /// Sets the weather the battle starts with.
+    pub fn set_weather(&mut self, weather: BattleWeather) {
+        self.weather = weather;
+    }
+
+    /// Sets the highest level each party member obeys the player at.
+    /// Pokemon without a cap always obey.
+    pub fn set_obedience(&mut self, obedience: Vec<Option<Level>>) {
+        self.obedience = obedience;
+    }
+
+    /// Whether a pokemon above the level it obeys up to ignores orders this turn.
+    pub fn disobeys<R: Rng>(random: &mut R, level: Level, cap: Level) -> bool {
+        level > cap && random.gen_range(0..256u32) * (level as u32 + cap as u32) / 256 >= cap as u32
+    }
+
+    /// Swaps the move a disobeying pokemon was told to use for another one it can use.
+    fn disobey(
+        pokemon: &OwnedPokemon,
+        action: &BattleMove<ID>,
+        roll: u32,
+    ) -> Option<BattleMove<ID>> {
+        match action {
+            BattleMove::Move(index, target) => {
+                let moves = pokemon
+                    .moves
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, m)| {
+                        i != index && m.pp() != 0 && (target.is_some() || !m.0.target.needs_input())
+                    })
+                    .map(|(i, ..)| i)
+                    .collect::<Vec<_>>();
+                match moves.is_empty() {
+                    true => None,
+                    false => Some(BattleMove::Move(
+                        moves[roll as usize % moves.len()],
+                        target.clone(),
+                    )),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes the pokemon caught since the battle started.
+    pub fn take_caught(&mut self) -> Vec<SavedPokemon> {
+        std::mem::take(&mut self.caught)
+    }
+
+    /// The player's party as it is now, including pokemon caught this battle.
+    pub fn party(&self) -> &[OwnedPokemon] {
+        match &self.local {
+            Some(local) => &local.player.pokemon,
+            None => &[],
+        }
+    }
+
+    /// Party members that were sent out since the battle started.
+    pub fn participants(&self) -> &[usize] {
+        &self.participants
+    }
+
+    /// Takes the opponent species revealed since the battle started.
+    pub fn take_seen(&mut self) -> Vec<PokemonId> {
+        std::mem::take(&mut self.seen)
     }
 
     pub fn reset_gui(&mut self) {
@@ -3,10 +3,10 @@ use std::sync::Arc;
 use pokengine::{
     engine::{
         graphics::{Color, Draw, DrawImages, DrawShapes, Texture},
-        text::{MessagePage, MessageState},
+        text::MessageState,
         App,
     },
-    pokedex::Money, texture::TrainerGroupTextures,
+    texture::TrainerGroupTextures,
 };
 
 use crate::{
@@ -74,22 +74,6 @@ impl BattleCloser {
                     }
                     None => {}
                 }
-
-                let gain = opponents
-                    .players
-                    .values()
-                    .flat_map(|o| o.trainer.as_ref().map(|t| t.worth))
-                    .sum::<Money>();
-
-                if gain != 0 {
-                    text.pages.push(MessagePage {
-                        lines: vec![
-                            format!("{} got ${}", local.player.name(), gain),
-                            String::from("for winning!"),
-                        ],
-                        ..Default::default()
-                    });
-                }
             }
             false => {}
         }
@@ -5,20 +5,19 @@ use pokengine::{
     engine::{egui, App},
     gui::{
         bag::{BagAction, BagGui},
+        move_info::MoveInfoPanel,
         party::{PartyAction, PartyGui},
     },
     pokedex::item::ItemId,
     texture::{ItemTextures, PokemonTextures},
 };
 
-pub mod move_info;
 pub mod moves;
 pub mod target;
 
 pub mod level;
 
 use self::{
-    move_info::MoveInfoPanel,
     moves::{ButtonState, MovePanel},
     target::TargetPanel,
 };
@@ -187,6 +186,7 @@ impl BattlePanel {
                                     self.targets.spawn();
                                 }
                             }
+                            BagAction::Give(..) => (),
                         }
                     }
                 } else {
@@ -203,6 +203,10 @@ impl BattlePanel {
                                     return Some(BattleAction::Action(BattleMove::Switch(p)));
                                 }
                             }
+                            PartyAction::Field(..)
+                            | PartyAction::Give(..)
+                            | PartyAction::Take(..)
+                            | PartyAction::Swap(..) => (),
                         }
                     }
                 } else {
@@ -268,6 +272,10 @@ impl BattlePanel {
                                     return Some(BattleAction::Action(BattleMove::Switch(p)));
                                 }
                             }
+                            PartyAction::Field(..)
+                            | PartyAction::Give(..)
+                            | PartyAction::Take(..)
+                            | PartyAction::Swap(..) => (),
                         }
                     }
                 } else {
@@ -15,6 +15,8 @@ use pokengine::{
     },
 };
 
+use crate::BattleWeather;
+
 pub type BattleMessageState = MessageStates<[f32; 4], ()>;
 
 #[derive(Default)]
@@ -58,6 +60,29 @@ impl BattleText {
         });
     }
 
+    pub(crate) fn on_disobey(&mut self, user: &str) {
+        let text = self.0.get_or_insert_with(MessageState::default);
+        text.pages.push(MessagePage {
+            lines: vec![format!("{} ignored orders!", user)],
+            wait: Some(0.5),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn on_weather(&mut self, weather: BattleWeather) {
+        let line = match weather {
+            BattleWeather::Clear => return,
+            BattleWeather::Rain => "It is raining.",
+            BattleWeather::Sun => "The sunlight is strong.",
+        };
+        let text = self.0.get_or_insert_with(MessageState::default);
+        text.pages.push(MessagePage {
+            lines: vec![line.to_owned()],
+            wait: Some(0.5),
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn on_effective(&mut self, effective: &Effective) {
         let text = self.0.get_or_insert_with(MessageState::default);
         if effective != &Effective::Effective {
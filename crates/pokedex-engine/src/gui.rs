@@ -1,8 +1,12 @@
 use std::ops::Deref;
 
 pub mod bag;
+pub mod evolution;
 pub mod health;
+pub mod learn;
+pub mod move_info;
 pub mod party;
+pub mod pokedex;
 pub mod pokemon;
 
 pub const LEVEL_PREFIX: &str = "Lv";
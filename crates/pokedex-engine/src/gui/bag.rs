@@ -16,10 +16,14 @@ pub struct BagGui {
     alive: bool,
     textures: Arc<ItemTextures>,
     select: select::BagSelect,
+    /// If items can be given to party members to hold
+    give: bool,
 }
 
 pub enum BagAction {
     Use(ItemId),
+    /// Give the item to a party member to hold
+    Give(ItemId),
 }
 
 impl BagGui {
@@ -30,9 +34,15 @@ impl BagGui {
             alive: Default::default(),
             textures,
             select: Default::default(),
+            give: false,
         }
     }
 
+    pub fn with_held_items(mut self) -> Self {
+        self.give = true;
+        self
+    }
+
     pub fn ui(&mut self, egui: &egui::Context, bag: &mut InitBag) -> Option<BagAction> {
         if self.alive {
             egui::Window::new("Bag").show(egui, |ui| {
@@ -70,11 +80,14 @@ impl BagGui {
             });
 
             if self.select.alive() {
-                if let Some(action) = self.select.ui(egui) {
+                if let Some(action) = self.select.ui(egui, self.give) {
                     match action {
                         select::SelectAction::Select(item) => {
                             return Some(BagAction::Use(item));
                         }
+                        select::SelectAction::Give(item) => {
+                            return Some(BagAction::Give(item));
+                        }
                     }
                 }
             }
@@ -10,6 +10,7 @@ pub struct BagSelect {
 
 pub enum SelectAction {
     Select(ItemId),
+    Give(ItemId),
 }
 
 impl BagSelect {
@@ -22,7 +23,7 @@ impl BagSelect {
         self.alive
     }
 
-    pub fn ui(&mut self, egui: &egui::Context) -> Option<SelectAction> {
+    pub fn ui(&mut self, egui: &egui::Context, give: bool) -> Option<SelectAction> {
         egui::Window::new("Bag: Select")
             .show(egui, |ui| {
                 if ui.button("Select").clicked() {
@@ -31,6 +32,12 @@ impl BagSelect {
                         return Some(SelectAction::Select(item));
                     }
                 }
+                if give && ui.button("Give").clicked() {
+                    if let Some(item) = self.item.take() {
+                        self.alive = false;
+                        return Some(SelectAction::Give(item));
+                    }
+                }
                 None
             })
             .and_then(|i| i.inner)
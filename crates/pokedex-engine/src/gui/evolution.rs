@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use crate::{
+    pokedex::pokemon::{PokemonId, PokemonTexture},
+    texture::PokemonTextures,
+    CRY_ID,
+};
+
+use engine::{
+    controls::{pressed, Control},
+    egui,
+    sound::{play_sound, SoundVariant},
+    App, Plugins,
+};
+
+/// Evolution scene, flashing between the old and new species.
+pub struct EvolutionGui {
+    evolving: Option<Evolving>,
+    textures: Arc<PokemonTextures>,
+}
+
+struct Evolving {
+    name: String,
+    from: PokemonId,
+    into: PokemonId,
+    into_name: String,
+    timer: f32,
+    state: EvolutionState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EvolutionState {
+    Evolving,
+    Evolved,
+    Cancelled,
+}
+
+impl EvolutionGui {
+    /// Seconds the sprites flash for before the pokemon evolves
+    const DURATION: f32 = 6.0;
+
+    pub fn new(textures: Arc<PokemonTextures>) -> Self {
+        Self {
+            evolving: None,
+            textures,
+        }
+    }
+
+    pub fn spawn(&mut self, name: String, from: PokemonId, into: PokemonId, into_name: String) {
+        self.evolving = Some(Evolving {
+            name,
+            from,
+            into,
+            into_name,
+            timer: 0.0,
+            state: EvolutionState::Evolving,
+        });
+    }
+
+    /// Returns whether the pokemon evolved once the player has closed the scene.
+    pub fn ui(
+        &mut self,
+        app: &mut App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        delta: f32,
+    ) -> Option<bool> {
+        let evolving = self.evolving.as_mut()?;
+
+        let mut done = false;
+
+        match evolving.state {
+            EvolutionState::Evolving => {
+                evolving.timer += delta;
+                if pressed(app, plugins, Control::B) {
+                    evolving.state = EvolutionState::Cancelled;
+                } else if evolving.timer > Self::DURATION {
+                    evolving.state = EvolutionState::Evolved;
+                    play_sound(app, plugins, CRY_ID, SoundVariant::Num(evolving.into as _));
+                }
+            }
+            EvolutionState::Evolved | EvolutionState::Cancelled => {
+                done = pressed(app, plugins, Control::A);
+            }
+        }
+
+        // flash faster the closer the pokemon is to evolving
+        let shown = match evolving.state {
+            EvolutionState::Evolving => {
+                let speed = 2.0 + evolving.timer * 2.0;
+                match (evolving.timer * speed) as u32 % 2 == 0 {
+                    true => evolving.from,
+                    false => evolving.into,
+                }
+            }
+            EvolutionState::Evolved => evolving.into,
+            EvolutionState::Cancelled => evolving.from,
+        };
+
+        egui::Window::new("Evolution")
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(egui, |ui| {
+                if let Some((id, size)) = self.textures.egui_id(&shown, PokemonTexture::Front) {
+                    ui.image(id, size);
+                }
+                ui.label(match evolving.state {
+                    EvolutionState::Evolving => format!("What? {} is evolving!", evolving.name),
+                    EvolutionState::Evolved => format!(
+                        "Congratulations! Your {} evolved into {}!",
+                        evolving.name, evolving.into_name
+                    ),
+                    EvolutionState::Cancelled => {
+                        format!("Huh? {} stopped evolving!", evolving.name)
+                    }
+                });
+                if evolving.state != EvolutionState::Evolving && ui.button("Ok").clicked() {
+                    done = true;
+                }
+            });
+
+        match done {
+            true => {
+                let evolved = evolving.state == EvolutionState::Evolved;
+                self.evolving = None;
+                Some(evolved)
+            }
+            false => None,
+        }
+    }
+
+    pub fn alive(&self) -> bool {
+        self.evolving.is_some()
+    }
+}
@@ -0,0 +1,161 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    gui::move_info::MoveInfoPanel,
+    pokedex::{
+        moves::{owned::OwnedMove, Move},
+        pokemon::owned::OwnedPokemon,
+    },
+};
+
+use engine::{
+    controls::{pressed, Control},
+    egui, App, Plugins,
+};
+
+/// Teaches moves to party pokemon, asking which move to forget when they already know four.
+/// Used for level up moves, TMs and move tutors.
+#[derive(Default)]
+pub struct LearnMoveGui {
+    /// Party index and the move it is trying to learn
+    queue: VecDeque<(usize, Arc<Move>)>,
+    state: LearnState,
+}
+
+enum LearnState {
+    Choosing,
+    /// Asking if the player wants to stop learning the move
+    GivingUp,
+    Message(String),
+}
+
+impl Default for LearnState {
+    fn default() -> Self {
+        Self::Choosing
+    }
+}
+
+impl LearnMoveGui {
+    pub const MAX_MOVES: usize = 4;
+
+    pub fn spawn(&mut self, pokemon: usize, m: Arc<Move>) {
+        self.queue.push_back((pokemon, m));
+    }
+
+    pub fn ui(
+        &mut self,
+        app: &App,
+        plugins: &Plugins,
+        egui: &egui::Context,
+        party: &mut [OwnedPokemon],
+    ) {
+        if let LearnState::Message(message) = &self.state {
+            let mut done = pressed(app, plugins, Control::A);
+            egui::Window::new("Learn Move")
+                .title_bar(false)
+                .show(egui, |ui| {
+                    ui.label(message);
+                    if ui.button("Ok").clicked() {
+                        done = true;
+                    }
+                });
+            if done {
+                self.state = LearnState::Choosing;
+            }
+            return;
+        }
+
+        let (index, m) = match self.queue.front() {
+            Some((index, m)) => (*index, m.clone()),
+            None => return,
+        };
+
+        let pokemon = match party.get_mut(index) {
+            Some(pokemon) if !pokemon.moves.iter().any(|known| known.0.id == m.id) => pokemon,
+            _ => {
+                self.queue.pop_front();
+                return;
+            }
+        };
+
+        if pokemon.moves.len() < Self::MAX_MOVES {
+            pokemon.moves.push(OwnedMove::from(m.clone()));
+            self.learned(format!("{} learned {}!", pokemon.name(), m.name));
+            return;
+        }
+
+        match self.state {
+            LearnState::Choosing => {
+                if pressed(app, plugins, Control::B) {
+                    self.state = LearnState::GivingUp;
+                    return;
+                }
+                let mut forget = None;
+                egui::Window::new("Learn Move")
+                    .title_bar(false)
+                    .show(egui, |ui| {
+                        ui.label(format!("{} is trying to learn {}.", pokemon.name(), m.name));
+                        ui.label(format!(
+                            "But {} can't learn more than four moves.",
+                            pokemon.name()
+                        ));
+                        ui.label(format!("Delete an older move to make room for {}?", m.name));
+                        ui.separator();
+                        ui.label(&m.name);
+                        MoveInfoPanel::ui(ui, &OwnedMove::from(m.clone()));
+                        ui.separator();
+                        for (i, known) in pokemon.moves.iter().enumerate() {
+                            if ui.button(format!("Forget {}", known.0.name)).clicked() {
+                                forget = Some(i);
+                            }
+                        }
+                        if ui.button("Give Up").clicked() {
+                            self.state = LearnState::GivingUp;
+                        }
+                    });
+                if let Some(i) = forget {
+                    let forgotten =
+                        std::mem::replace(&mut pokemon.moves[i], OwnedMove::from(m.clone()));
+                    self.learned(format!(
+                        "1, 2, and... Poof! {} forgot {}. And... {} learned {}!",
+                        pokemon.name(),
+                        forgotten.0.name,
+                        pokemon.name(),
+                        m.name
+                    ));
+                }
+            }
+            LearnState::GivingUp => {
+                let mut answer = None;
+                egui::Window::new("Learn Move")
+                    .title_bar(false)
+                    .show(egui, |ui| {
+                        ui.label(format!("Stop learning {}?", m.name));
+                        if ui.button("Yes").clicked() || pressed(app, plugins, Control::A) {
+                            answer = Some(true);
+                        } else if ui.button("No").clicked() || pressed(app, plugins, Control::B) {
+                            answer = Some(false);
+                        }
+                    });
+                match answer {
+                    Some(true) => {
+                        self.learned(format!("{} did not learn {}.", pokemon.name(), m.name))
+                    }
+                    Some(false) => self.state = LearnState::Choosing,
+                    None => (),
+                }
+            }
+            LearnState::Message(..) => (),
+        }
+    }
+
+    /// Finishes the current move and shows the result.
+    fn learned(&mut self, message: String) {
+        self.queue.pop_front();
+        self.state = LearnState::Message(message);
+    }
+
+    pub fn alive(&self) -> bool {
+        !self.queue.is_empty() || matches!(self.state, LearnState::Message(..))
+    }
+}
@@ -0,0 +1,56 @@
+use std::io::Write;
+
+use crate::pokedex::moves::owned::OwnedMove;
+
+use engine::egui;
+
+pub struct MoveInfoPanel;
+
+impl MoveInfoPanel {
+    pub fn ui(ui: &mut egui::Ui, m: &OwnedMove) {
+        egui::Grid::new("MoveInfoGrid").show(ui, |ui| {
+            let mut pp = [0u8; 8];
+            if let Ok(()) = write!(&mut pp as &mut [u8], "PP {}/{}", m.pp(), m.0.pp) {
+                if let Ok(str) = std::str::from_utf8(&pp) {
+                    ui.label(str);
+                }
+            }
+            ui.end_row();
+            let mut move_type = [0u8; 16];
+            if let Ok(()) = write!(&mut move_type as &mut [u8], "TYPE/{:?}", m.0.pokemon_type) {
+                if let Ok(str) = std::str::from_utf8(&move_type) {
+                    ui.label(str);
+                }
+            }
+            ui.end_row();
+            let mut category = [0u8; 16];
+            if let Ok(()) = write!(&mut category as &mut [u8], "{:?}", m.0.category) {
+                if let Ok(str) = std::str::from_utf8(&category) {
+                    ui.label(str);
+                }
+            }
+            ui.end_row();
+            let mut power = [0u8; 16];
+            let written = match m.0.power {
+                Some(value) => write!(&mut power as &mut [u8], "POWER {}", value),
+                None => write!(&mut power as &mut [u8], "POWER ---"),
+            };
+            if let Ok(()) = written {
+                if let Ok(str) = std::str::from_utf8(&power) {
+                    ui.label(str);
+                }
+            }
+            ui.end_row();
+            let mut accuracy = [0u8; 16];
+            let written = match m.0.accuracy {
+                Some(value) => write!(&mut accuracy as &mut [u8], "ACCURACY {}", value),
+                None => write!(&mut accuracy as &mut [u8], "ACCURACY ---"),
+            };
+            if let Ok(()) = written {
+                if let Ok(str) = std::str::from_utf8(&accuracy) {
+                    ui.label(str);
+                }
+            }
+        });
+    }
+}
@@ -4,7 +4,10 @@ use std::sync::{
 };
 
 use crate::{
-    pokedex::pokemon::{owned::OwnedPokemon, PokemonTexture},
+    pokedex::{
+        moves::MoveId,
+        pokemon::{owned::OwnedPokemon, PokemonTexture},
+    },
     texture::PokemonTextures,
 };
 
@@ -20,10 +23,21 @@ pub struct PartyGui {
     swap: Mutex<Option<usize>>,
     accumulator: Mutex<f32>,
     textures: Arc<PokemonTextures>,
+    /// Moves that can be used from the select menu
+    field: Vec<MoveId>,
+    /// If held items can be given and taken from the select menu
+    held: bool,
 }
 
 pub enum PartyAction {
     Select(usize),
+    Field(usize, MoveId),
+    /// Pick an item from the bag for the pokemon to hold
+    Give(usize),
+    /// Put the pokemon's held item in the bag
+    Take(usize),
+    /// Two party members were swapped
+    Swap(usize, usize),
 }
 
 impl PartyGui {
@@ -35,9 +49,21 @@ impl PartyGui {
             swap: Default::default(),
             summary: summary::SummaryGui::new(textures.clone()),
             textures,
+            field: Default::default(),
+            held: false,
         }
     }
 
+    pub fn with_field_moves(mut self, moves: Vec<MoveId>) -> Self {
+        self.field = moves;
+        self
+    }
+
+    pub fn with_held_items(mut self) -> Self {
+        self.held = true;
+        self
+    }
+
     pub fn ui(
         &self,
         egui: &egui::Context,
@@ -52,11 +78,19 @@ impl PartyGui {
                 }
             }
 
-            if let Some(action) = self.select.ui(egui) {
+            let pokemon = self.select.pokemon.load(Ordering::Relaxed);
+            if let Some(action) = self
+                .select
+                .ui(egui, party.get(pokemon), &self.field, self.held)
+            {
                 self.select.despawn();
-                let pokemon = self.select.pokemon.load(Ordering::Relaxed);
                 match action {
                     select::SelectAction::Select => return Some(PartyAction::Select(pokemon)),
+                    select::SelectAction::Field(id) => {
+                        return Some(PartyAction::Field(pokemon, id))
+                    }
+                    select::SelectAction::Give => return Some(PartyAction::Give(pokemon)),
+                    select::SelectAction::Take => return Some(PartyAction::Take(pokemon)),
                     select::SelectAction::Summary => {
                         self.summary.spawn(pokemon);
                     }
@@ -106,7 +140,7 @@ impl PartyGui {
                                                     swap,
                                                     num,
                                                 );
-                                                return None;
+                                                return Some(PartyAction::Swap(swap, num));
                                             }
                                             None => {
                                                 if !self.select.alive() {
@@ -132,6 +166,10 @@ impl PartyGui {
                                     });
                                 }
 
+                                if let Some(item) = &pokemon.item {
+                                    ui.label(&item.name);
+                                }
+
                                 ui.end_row();
                             }
                             None
@@ -2,6 +2,8 @@ use std::sync::atomic::{AtomicBool, Ordering, AtomicUsize};
 
 use engine::egui;
 
+use crate::pokedex::{moves::MoveId, pokemon::owned::OwnedPokemon};
+
 #[derive(Default)]
 pub struct PartySelectMenu {
     alive: AtomicBool,
@@ -12,7 +14,9 @@ pub enum SelectAction {
     Select,
     Summary,
     Swap,
-    // Item,
+    Field(MoveId),
+    Give,
+    Take,
     // Cancel,
 }
 
@@ -30,11 +34,26 @@ impl PartySelectMenu {
         self.alive.load(Ordering::Relaxed)
     }
 
-    pub fn ui(&self, egui: &egui::Context) -> Option<SelectAction> {
+    pub fn ui(
+        &self,
+        egui: &egui::Context,
+        pokemon: Option<&OwnedPokemon>,
+        field: &[MoveId],
+        held: bool,
+    ) -> Option<SelectAction> {
         if self.alive() {
             egui::Window::new("Select")
                 .title_bar(false)
                 .show(egui, |ui| {
+                    for m in pokemon
+                        .into_iter()
+                        .flat_map(|pokemon| pokemon.moves.iter())
+                        .filter(|m| field.contains(&m.0.id))
+                    {
+                        if ui.button(m.0.name.to_uppercase()).clicked() {
+                            return Some(SelectAction::Field(m.0.id));
+                        }
+                    }
                     if ui.button("Select").clicked() {
                         return Some(SelectAction::Select);
                     }
@@ -44,7 +63,14 @@ impl PartySelectMenu {
                     if ui.button("Swap").clicked() {
                         return Some(SelectAction::Swap);
                     }
-                    if ui.button("Give").clicked() {}
+                    if held {
+                        if ui.button("Give").clicked() {
+                            return Some(SelectAction::Give);
+                        }
+                        if ui.button("Take").clicked() {
+                            return Some(SelectAction::Take);
+                        }
+                    }
                     None
                 })
                 .and_then(|i| i.inner)
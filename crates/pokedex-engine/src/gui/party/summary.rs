@@ -80,6 +80,10 @@ impl SummaryGui {
                             }
                             ui.label(pokemon.name());
                             ui.label(format!("Lv. {}", pokemon.level));
+                            ui.label(match &pokemon.item {
+                                Some(item) => format!("Item: {}", item.name),
+                                None => "Item: None".to_owned(),
+                            });
                         }
                         1 => {}
                         2 => {
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use crate::{
+    pokedex::{
+        pokemon::{Pokemon, PokemonId, PokemonTexture},
+        Dex,
+    },
+    texture::PokemonTextures,
+    CRY_ID,
+};
+
+use engine::{
+    controls::{pressed, Control},
+    egui,
+    sound::{play_sound, SoundVariant},
+    App, HashMap, HashSet, Plugins,
+};
+
+/// Names of the places each species can be found in the wild.
+pub type DexAreas = HashMap<PokemonId, Vec<String>>;
+
+pub struct PokedexGui {
+    alive: bool,
+    order: DexOrder,
+    selected: Option<PokemonId>,
+    page: DexPage,
+    textures: Arc<PokemonTextures>,
+    areas: DexAreas,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DexOrder {
+    /// Every number up to the highest seen, including unseen species
+    Numerical,
+    /// Only seen species, sorted by name
+    Alphabetical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DexPage {
+    Info,
+    Area,
+}
+
+impl PokedexGui {
+    const UNKNOWN: &'static str = "----------";
+
+    pub fn new(textures: Arc<PokemonTextures>, areas: DexAreas) -> Self {
+        Self {
+            alive: false,
+            order: DexOrder::Numerical,
+            selected: None,
+            page: DexPage::Info,
+            textures,
+            areas,
+        }
+    }
+
+    pub fn ui(
+        &mut self,
+        app: &mut App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        pokedex: &Dex<Pokemon>,
+        seen: &HashSet<PokemonId>,
+        caught: &HashSet<PokemonId>,
+    ) {
+        if !self.alive {
+            return;
+        }
+
+        if pressed(app, plugins, Control::B) {
+            match self.selected.is_some() {
+                true => self.selected = None,
+                false => self.despawn(),
+            }
+            return;
+        }
+
+        match self.selected.and_then(|id| pokedex.try_get(&id)) {
+            Some(pokemon) => self.detail(app, plugins, egui, pokemon, caught),
+            None => self.list(egui, pokedex, seen, caught),
+        }
+    }
+
+    fn list(
+        &mut self,
+        egui: &egui::Context,
+        pokedex: &Dex<Pokemon>,
+        seen: &HashSet<PokemonId>,
+        caught: &HashSet<PokemonId>,
+    ) {
+        egui::Window::new("Pokedex").show(egui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("Seen: {}", seen.len()));
+                ui.label(format!("Owned: {}", caught.len()));
+            });
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.order, DexOrder::Numerical, "Numerical");
+                ui.selectable_value(&mut self.order, DexOrder::Alphabetical, "A to Z");
+                if ui.button("Close").clicked() {
+                    self.alive = false;
+                }
+            });
+            ui.separator();
+
+            let entries = match self.order {
+                DexOrder::Numerical => {
+                    let last = seen.iter().max().copied().unwrap_or_default();
+                    (0..=last)
+                        .filter_map(|id| pokedex.try_get(&id))
+                        .collect::<Vec<_>>()
+                }
+                DexOrder::Alphabetical => {
+                    let mut entries = seen
+                        .iter()
+                        .flat_map(|id| pokedex.try_get(id))
+                        .collect::<Vec<_>>();
+                    entries.sort_by(|a, b| a.name.cmp(&b.name));
+                    entries
+                }
+            };
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("Pokedex Entries").show(ui, |ui| {
+                    for pokemon in entries {
+                        let known = seen.contains(&pokemon.id);
+                        ui.label(format!("No{:03}", pokemon.id));
+                        ui.label(match caught.contains(&pokemon.id) {
+                            true => "*",
+                            false => "",
+                        });
+                        let name = match known {
+                            true => pokemon.name.as_str(),
+                            false => Self::UNKNOWN,
+                        };
+                        if ui.add_enabled(known, egui::Button::new(name)).clicked() {
+                            self.selected = Some(pokemon.id);
+                            self.page = DexPage::Info;
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+    }
+
+    fn detail(
+        &mut self,
+        app: &mut App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        pokemon: &Pokemon,
+        caught: &HashSet<PokemonId>,
+    ) {
+        egui::Window::new("Pokedex Entry").show(egui, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.page, DexPage::Info, "Info");
+                ui.selectable_value(&mut self.page, DexPage::Area, "Area");
+                if ui.button("Cry").clicked() {
+                    play_sound(app, plugins, CRY_ID, SoundVariant::Num(pokemon.id as _));
+                }
+                if ui.button("Back").clicked() {
+                    self.selected = None;
+                }
+            });
+            ui.separator();
+            ui.label(format!("No{:03} {}", pokemon.id, pokemon.name));
+            match self.page {
+                DexPage::Info => {
+                    if let Some((id, size)) =
+                        self.textures.egui_id(&pokemon.id, PokemonTexture::Front)
+                    {
+                        ui.image(id, size);
+                    }
+                    // only pokemon the player has owned show their details
+                    match caught.contains(&pokemon.id) {
+                        true => {
+                            ui.label(format!("{} Pokemon", pokemon.species));
+                            ui.label(match pokemon.types.secondary {
+                                Some(secondary) => {
+                                    format!("Type: {:?}/{:?}", pokemon.types.primary, secondary)
+                                }
+                                None => format!("Type: {:?}", pokemon.types.primary),
+                            });
+                            ui.label(format!("HT {:.1} m", pokemon.height as f32 / 10.0));
+                            ui.label(format!("WT {:.1} kg", pokemon.weight as f32 / 10.0));
+                        }
+                        false => {
+                            ui.label("? Pokemon");
+                            ui.label("HT ??? m");
+                            ui.label("WT ??? kg");
+                        }
+                    }
+                }
+                DexPage::Area => match self.areas.get(&pokemon.id) {
+                    Some(areas) if !areas.is_empty() => {
+                        for area in areas {
+                            ui.label(area);
+                        }
+                    }
+                    _ => {
+                        ui.label("Area Unknown");
+                    }
+                },
+            }
+        });
+    }
+
+    pub fn spawn(&mut self) {
+        self.alive = true;
+        self.selected = None;
+    }
+
+    pub fn despawn(&mut self) {
+        self.alive = false;
+    }
+
+    pub fn alive(&self) -> bool {
+        self.alive
+    }
+}
@@ -0,0 +1,253 @@
+//! Crash safe storage with a checksum header and a rotating backup.
+
+use serde::de::DeserializeOwned;
+
+use crate::{error::DataError, DataSerializer};
+
+/// Written as a comment so checked files can still be read as plain ron.
+const HEADER: &str = "// checksum ";
+
+/// FNV-1a hash of the data.
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn seal(data: Vec<u8>) -> Vec<u8> {
+    let mut sealed = format!("{}{:016x}\n", HEADER, checksum(&data)).into_bytes();
+    sealed.extend(data);
+    sealed
+}
+
+/// Strips and checks the header. Data without a header is rejected,
+/// since a file cut off before its header can't be told apart from an unchecked one.
+fn verify(data: &[u8]) -> Result<&[u8], DataError> {
+    let data = data
+        .strip_prefix(HEADER.as_bytes())
+        .ok_or(DataError::Checksum)?;
+    let split = data
+        .iter()
+        .position(|b| *b == b'\n')
+        .ok_or(DataError::Checksum)?;
+    let expected = core::str::from_utf8(&data[..split])?;
+    let data = &data[split + 1..];
+    match u64::from_str_radix(expected, 16) == Ok(checksum(data)) {
+        true => Ok(data),
+        false => Err(DataError::Checksum),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use super::*;
+
+    fn sibling(path: &Path, suffix: &str) -> PathBuf {
+        let mut path = path.as_os_str().to_owned();
+        path.push(suffix);
+        path.into()
+    }
+
+    fn backup(path: &Path) -> PathBuf {
+        sibling(path, ".bak")
+    }
+
+    fn open<S: DataSerializer, D: DeserializeOwned>(path: &Path) -> Result<D, DataError> {
+        S::deserialize(verify(&std::fs::read(path)?)?)
+    }
+
+    fn raw(path: &Path) -> Result<Vec<u8>, DataError> {
+        let data = std::fs::read(path)?;
+        verify(&data).map(<[u8]>::to_vec)
+    }
+
+    /// Reads the file, falling back to its backup if it is missing or invalid.
+    pub fn read<S: DataSerializer, D: DeserializeOwned>(
+        path: &Path,
+    ) -> Result<Option<D>, DataError> {
+        let backup = backup(path);
+        if !path.exists() && !backup.exists() {
+            return Ok(None);
+        }
+        match open::<S, D>(path) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) => open::<S, D>(&backup).map(Some).map_err(|_| err),
+        }
+    }
+
+    /// Reads the checked contents of the file, falling back to its backup if it is missing or invalid.
+    pub fn read_raw(path: &Path) -> Result<Option<Vec<u8>>, DataError> {
+        let backup = backup(path);
+        if !path.exists() && !backup.exists() {
+            return Ok(None);
+        }
+        raw(path)
+            .or_else(|err| raw(&backup).map_err(|_| err))
+            .map(Some)
+    }
+
+    /// Writes to a temporary file, syncs it and renames it over the target.
+    fn replace(path: &Path, data: &[u8]) -> Result<(), DataError> {
+        let temp = sibling(path, ".tmp");
+        {
+            let mut file = std::fs::File::create(&temp)?;
+            file.write_all(data)?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Replaces the file with the sealed data, keeping the last valid file as a backup.
+    pub fn write(path: &Path, data: Vec<u8>) -> Result<(), DataError> {
+        if let Ok(current) = std::fs::read(path) {
+            if verify(&current).is_ok() {
+                replace(&backup(path), &current)?;
+            }
+        }
+        replace(path, &seal(data))
+    }
+
+    /// Copies the valid contents of one file, or its backup, to another.
+    pub fn copy(from: &Path, to: &Path) -> Result<(), DataError> {
+        let data = raw(from).or_else(|err| raw(&backup(from)).map_err(|_| err))?;
+        write(to, data)
+    }
+
+    pub fn remove(path: &Path) -> Result<(), DataError> {
+        for path in [path.to_owned(), backup(path)] {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use general_storage_web::{LocalStorage, Storage};
+
+    use super::*;
+
+    fn backup(key: &str) -> String {
+        format!("{}.bak", key)
+    }
+
+    fn open<S: DataSerializer, D: DeserializeOwned>(key: &str) -> Result<D, DataError> {
+        S::deserialize(&raw(key)?)
+    }
+
+    fn raw(key: &str) -> Result<Vec<u8>, DataError> {
+        let data = LocalStorage::new()
+            .load_raw(key)
+            .map_err(|_| DataError::QuadStorageError)?;
+        verify(&data).map(<[u8]>::to_vec)
+    }
+
+    /// Reads the key, falling back to its backup if it is missing or invalid.
+    pub fn read<S: DataSerializer, D: DeserializeOwned>(key: &str) -> Result<Option<D>, DataError> {
+        let storage = LocalStorage::new();
+        let backup = backup(key);
+        if !storage.exists(key) && !storage.exists(&backup) {
+            return Ok(None);
+        }
+        match open::<S, D>(key) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) => open::<S, D>(&backup).map(Some).map_err(|_| err),
+        }
+    }
+
+    /// Reads the checked value of the key, falling back to its backup if it is missing or invalid.
+    pub fn read_raw(key: &str) -> Result<Option<Vec<u8>>, DataError> {
+        let storage = LocalStorage::new();
+        let backup = backup(key);
+        if !storage.exists(key) && !storage.exists(&backup) {
+            return Ok(None);
+        }
+        raw(key)
+            .or_else(|err| raw(&backup).map_err(|_| err))
+            .map(Some)
+    }
+
+    /// Writes the key, keeping the last valid value as a backup.
+    pub fn write(key: &str, data: Vec<u8>) -> Result<(), DataError> {
+        let mut storage = LocalStorage::new();
+        if let Ok(current) = storage.load_raw(key) {
+            if verify(&current).is_ok() {
+                storage
+                    .store_raw(backup(key), current)
+                    .map_err(|_| DataError::QuadStorageError)?;
+            }
+        }
+        storage
+            .store_raw(key, seal(data))
+            .map_err(|_| DataError::QuadStorageError)
+    }
+
+    /// Copies the valid value of one key, or its backup, to another.
+    pub fn copy(from: &str, to: &str) -> Result<(), DataError> {
+        let data = raw(from).or_else(|err| raw(&backup(from)).map_err(|_| err))?;
+        write(to, data)
+    }
+
+    pub fn remove(key: &str) -> Result<(), DataError> {
+        let mut storage = LocalStorage::new();
+        for key in [key.to_owned(), backup(key)] {
+            if storage.exists(&key) {
+                storage
+                    .remove(&key)
+                    .map_err(|_| DataError::QuadStorageError)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"(name: \"Red\", rival: \"Blue\")";
+
+    #[test]
+    fn round_trip() {
+        let sealed = seal(DATA.to_vec());
+        assert!(sealed.starts_with(HEADER.as_bytes()));
+        assert_eq!(verify(&sealed).unwrap(), DATA);
+    }
+
+    #[test]
+    fn tampered() {
+        let mut sealed = seal(DATA.to_vec());
+        let last = sealed.len() - 2;
+        sealed[last] = b'!';
+        assert!(matches!(verify(&sealed), Err(DataError::Checksum)));
+    }
+
+    #[test]
+    fn truncated() {
+        let sealed = seal(DATA.to_vec());
+        assert!(matches!(
+            verify(&sealed[..sealed.len() - 4]),
+            Err(DataError::Checksum)
+        ));
+        assert!(matches!(verify(&sealed[..8]), Err(DataError::Checksum)));
+    }
+
+    #[test]
+    fn missing_header() {
+        assert!(matches!(verify(DATA), Err(DataError::Checksum)));
+    }
+}
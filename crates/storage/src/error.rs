@@ -4,6 +4,8 @@ pub enum DataError {
     Utf8Error(std::str::Utf8Error),
     Text(ron::Error),
     Bytes(postcard::Error),
+    /// Stored data is missing its checksum or does not match it
+    Checksum,
     // #[cfg(target_arch = "wasm32")]
     #[cfg(all(feature = "io", target_arch = "wasm32"))]
     QuadStorageError,
@@ -6,6 +6,8 @@ use std::path::PathBuf;
 pub use postcard::{from_bytes, to_allocvec as to_bytes};
 pub use ron::{from_str, to_string};
 
+#[cfg(feature = "io")]
+mod atomic;
 pub mod error;
 pub mod reload;
 
@@ -33,40 +35,16 @@ pub fn try_load<S: DataSerializer, D: PersistantData>(
     publisher: Option<&str>,
     application: &str,
 ) -> Result<D, error::DataError> {
-    let path = crate::directory(false, publisher, application)?.join(file::<S, D>());
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let data = match path.exists() {
-            true => {
-                let data = std::fs::read(&path)?;
-                S::deserialize(&data)?
-            }
-            false => {
-                let data = D::default();
-                if let Err(err) = save::<S, D>(&data, publisher, application) {
-                    let name = std::any::type_name::<D>();
-                    let name = name.split("::").last().unwrap_or(name);
-                }
-                data
-            }
-        };
-        Ok(data)
-    }
-    #[cfg(target_arch = "wasm32")]
-    {
-        use general_storage_web::Storage;
-        let data = general_storage_web::LocalStorage::new().load_raw(D::path());
-        match data {
-            Ok(data) => Ok(S::deserialize(&data)?),
-            Err(err) => {
-                let data = D::default();
-                if let Err(err) = save::<S, D>(&data, publisher, application) {
-                    let name = std::any::type_name::<D>();
-                    let name = name.split("::").last().unwrap_or(name);
-                    // warn!("Could not save new {} with error {}", name, err);
-                }
-                Ok(data)
+    match load_as::<S, D>(D::path(), publisher, application)? {
+        Some(data) => Ok(data),
+        None => {
+            let data = D::default();
+            if let Err(err) = save::<S, D>(&data, publisher, application) {
+                let name = std::any::type_name::<D>();
+                let name = name.split("::").last().unwrap_or(name);
+                // warn!("Could not save new {} with error {}", name, err);
             }
+            Ok(data)
         }
     }
 }
@@ -82,27 +60,87 @@ pub fn save<S: DataSerializer, D: PersistantData>(
     publisher: Option<&str>,
     application: &str,
 ) -> Result<(), error::DataError> {
-    let data = S::serialize(data)?;
+    save_as::<S, D>(data, D::path(), publisher, application)
+}
 
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let dir = self::directory(false, publisher, application)?;
-        let path = dir.join(format!("{}.{}", D::path(), S::extension()));
+/// Where data saved under a name is stored.
+#[cfg(all(feature = "io", not(target_arch = "wasm32")))]
+fn location<S: DataSerializer>(
+    name: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<PathBuf, error::DataError> {
+    let dir = self::directory(false, publisher, application)?;
+    Ok(dir.join(format!("{}.{}", name, S::extension())))
+}
 
-        if !path.exists() {
-            std::fs::create_dir_all(&dir)?;
-        }
+/// Where data saved under a name is stored.
+#[cfg(all(feature = "io", target_arch = "wasm32"))]
+fn location<S: DataSerializer>(
+    name: &str,
+    _publisher: Option<&str>,
+    _application: &str,
+) -> Result<String, error::DataError> {
+    Ok(name.to_owned())
+}
 
-        std::fs::write(&path, data)?;
-    }
+/// Saves data under a name instead of a [PersistantData] path.
+#[cfg(feature = "io")]
+pub fn save_as<S: DataSerializer, D: Serialize>(
+    data: &D,
+    name: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<(), error::DataError> {
+    atomic::write(
+        &location::<S>(name, publisher, application)?,
+        S::serialize(data)?,
+    )
+}
 
-    #[cfg(target_arch = "wasm32")]
-    {
-        use general_storage_web::Storage;
-        general_storage_web::LocalStorage::new().store_raw(D::path(), data);
-    }
+/// Loads data saved with [save_as]. Returns `None` if nothing is saved under the name.
+#[cfg(feature = "io")]
+pub fn load_as<S: DataSerializer, D: DeserializeOwned>(
+    name: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<Option<D>, error::DataError> {
+    atomic::read::<S, D>(&location::<S>(name, publisher, application)?)
+}
+
+/// Loads the bytes saved with [save_as] without deserializing them,
+/// so callers can pick how to read them. Returns `None` if nothing is saved under the name.
+#[cfg(feature = "io")]
+pub fn load_raw_as<S: DataSerializer>(
+    name: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<Option<Vec<u8>>, error::DataError> {
+    atomic::read_raw(&location::<S>(name, publisher, application)?)
+}
 
-    Ok(())
+/// Copies data saved with [save_as] to another name.
+#[cfg(feature = "io")]
+pub fn copy_as<S: DataSerializer>(
+    from: &str,
+    to: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<(), error::DataError> {
+    atomic::copy(
+        &location::<S>(from, publisher, application)?,
+        &location::<S>(to, publisher, application)?,
+    )
+}
+
+/// Removes data saved with [save_as] along with its backup.
+#[cfg(feature = "io")]
+pub fn remove_as<S: DataSerializer>(
+    name: &str,
+    publisher: Option<&str>,
+    application: &str,
+) -> Result<(), error::DataError> {
+    atomic::remove(&location::<S>(name, publisher, application)?)
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -0,0 +1,87 @@
+use crate::engine::{
+    controls::{pressed, Control},
+    egui, App, Plugins,
+};
+
+use worldlib::{character::player::PlayerCharacter, map::data::BadgeData};
+
+/// Shows the gym badges the player has earned and when they earned them.
+#[derive(Default)]
+pub struct BadgeCaseGui {
+    alive: bool,
+}
+
+impl BadgeCaseGui {
+    const UNKNOWN: &'static str = "--";
+
+    pub fn ui(
+        &mut self,
+        app: &App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        badges: &BadgeData,
+        player: &PlayerCharacter,
+    ) {
+        if !self.alive {
+            return;
+        }
+
+        if pressed(app, plugins, Control::B) {
+            self.despawn();
+            return;
+        }
+
+        let mut case = badges.iter().collect::<Vec<_>>();
+        case.sort_by_key(|(.., badge)| badge.order);
+
+        egui::Window::new("Badges").show(egui, |ui| {
+            ui.label(format!("Badges: {}", player.badges.len()));
+            ui.separator();
+            egui::Grid::new("Badge Case").show(ui, |ui| {
+                for (id, badge) in case {
+                    let earned = player.badges.contains(id);
+                    ui.add_enabled(earned, egui::Label::new(badge.name.as_str()));
+                    ui.label(match (earned, player.badge_dates.get(id)) {
+                        (true, Some(date)) => Self::date(*date),
+                        _ => Self::UNKNOWN.to_owned(),
+                    });
+                    ui.end_row();
+                }
+            });
+            if ui.button("Close").clicked() {
+                self.alive = false;
+            }
+        });
+    }
+
+    /// Formats seconds since the unix epoch as a YYYY-MM-DD date.
+    pub fn date(seconds: u64) -> String {
+        if seconds == 0 {
+            return Self::UNKNOWN.to_owned();
+        }
+        // days to civil date, from Howard Hinnant's date algorithms
+        let days = (seconds / 86400) as i64 + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    pub fn spawn(&mut self) {
+        self.alive = true;
+    }
+
+    pub fn despawn(&mut self) {
+        self.alive = false;
+    }
+
+    pub fn alive(&self) -> bool {
+        self.alive
+    }
+}
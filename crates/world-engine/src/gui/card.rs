@@ -0,0 +1,99 @@
+use crate::engine::{
+    controls::{pressed, Control},
+    egui, App, Plugins,
+};
+
+use worldlib::{character::player::PlayerCharacter, map::data::BadgeData, pokedex::Money};
+
+/// The player's trainer card, summarizing their progress.
+#[derive(Default)]
+pub struct TrainerCardGui {
+    alive: bool,
+}
+
+impl TrainerCardGui {
+    pub fn ui(
+        &mut self,
+        app: &App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        player: &PlayerCharacter,
+        money: Money,
+        badges: &BadgeData,
+    ) {
+        if !self.alive {
+            return;
+        }
+
+        if pressed(app, plugins, Control::B) {
+            self.despawn();
+            return;
+        }
+
+        egui::Window::new("Trainer Card")
+            .title_bar(false)
+            .show(egui, |ui| {
+                Self::card(ui, player, money, badges);
+                if ui.button("Close").clicked() {
+                    self.alive = false;
+                }
+            });
+    }
+
+    /// Draws the card's contents, for use inside other windows.
+    pub fn card(ui: &mut egui::Ui, player: &PlayerCharacter, money: Money, badges: &BadgeData) {
+        egui::Grid::new("Trainer Card").show(ui, |ui| {
+            ui.label("Name");
+            ui.label(&player.name);
+            ui.end_row();
+            ui.label("IDNo.");
+            ui.label(match player.id {
+                Some(id) => format!("{:05}", id),
+                None => "-----".to_owned(),
+            });
+            ui.end_row();
+            ui.label("Money");
+            ui.label(format!("${}", money));
+            ui.end_row();
+            ui.label("Pokedex");
+            ui.label(player.dex.caught.len().to_string());
+            ui.end_row();
+            ui.label("Time");
+            ui.label(Self::time(player.play_time));
+            ui.end_row();
+        });
+
+        let mut earned = badges
+            .iter()
+            .filter(|(id, ..)| player.badges.contains(*id))
+            .map(|(.., badge)| badge)
+            .collect::<Vec<_>>();
+        earned.sort_by_key(|badge| badge.order);
+
+        ui.separator();
+        ui.label(format!("Badges: {}", earned.len()));
+        ui.horizontal_wrapped(|ui| {
+            for badge in earned {
+                ui.label(&badge.name);
+            }
+        });
+    }
+
+    /// Formats seconds as hours and minutes.
+    fn time(seconds: f64) -> String {
+        let minutes = (seconds / 60.0) as u64;
+        format!("{}:{:02}", minutes / 60, minutes % 60)
+    }
+
+    pub fn spawn(&mut self) {
+        self.alive = true;
+    }
+
+    pub fn despawn(&mut self) {
+        self.alive = false;
+    }
+
+    pub fn alive(&self) -> bool {
+        self.alive
+    }
+}
@@ -1,117 +1,204 @@
-use engine::{
-    utils::{Entity, Reset, HEIGHT},
-    input::{pressed, Control},
-    graphics::{draw_rectangle_lines, draw_rectangle, draw_text_left},
-    {
-        Context,
-        math::Vec2,
-        graphics::Color,
-    },
+use crate::engine::{
+    controls::{pressed, Control},
+    egui, App, Plugins,
 };
-use deps::hash::HashMap;
 
 use worldlib::{
-    map::MapIcon,
+    map::{data::WorldMaps, MapKind, RegionSection},
     positions::Location,
+    state::map::MapState,
 };
 
-pub type Locations = HashMap<MapIcon, (String, Location)>;
-
-#[derive(Default)]
+/// The town map, which can also be used to pick a town to fly to.
 pub struct WorldMapGui {
     alive: bool,
-
-    selected: Vec2<u8>,
-
-    locations: HashMap<Vec2<u8>, (MapIconInit, String, Location)>,
-
-    // visited: Vec<Location>,
-    // fly: Option<Vec<Location>>,
+    fly: bool,
+    cursor: (u8, u8),
+    locations: Vec<MapLocation>,
 }
 
-enum MapIconInit {
-    City,
-    Route(Vec2<u8>),
+struct MapLocation {
+    section: RegionSection,
+    name: String,
+    location: Location,
+    town: bool,
 }
 
 impl WorldMapGui {
-
-    pub const GUI_TILE_SIZE: f32 = 8.0;
-    pub const GUI_TILE_SIZE_MASK: u8 = 3;
-
-    pub fn add_locations(&mut self, locations: Locations) {
-        self.locations = locations.into_iter().map(|(i, (s, l))| {
-                let (i, si) = match i {
-                    MapIcon::City(x, y) => (Vec2::new(x, y), MapIconInit::City),
-                    MapIcon::Route(x, y, sx, sy) => (Vec2::new(x, y), MapIconInit::Route(Vec2::new(sx, sy))),
+    pub const WIDTH: u8 = 22;
+    pub const HEIGHT: u8 = 15;
+
+    const TILE_SIZE: f32 = 8.0;
+
+    pub fn new(maps: &WorldMaps) -> Self {
+        let mut locations = Vec::<MapLocation>::new();
+
+        for map in maps
+            .values()
+            .filter(|map| map.settings.kind == MapKind::Outdoor)
+        {
+            if let Some(section) = map.settings.region {
+                let location = MapLocation {
+                    section,
+                    name: map.name.clone(),
+                    location: map.id,
+                    town: map.settings.fly_position.is_some(),
                 };
-                (i, (si, s, l))
+                match locations.iter_mut().find(|l| l.section == section) {
+                    Some(existing) => {
+                        // towns take priority over the routes inside them
+                        if location.town && !existing.town {
+                            *existing = location;
+                        }
+                    }
+                    None => locations.push(location),
+                }
             }
-        ).collect();
+        }
+
+        Self {
+            alive: false,
+            fly: false,
+            cursor: Default::default(),
+            locations,
+        }
     }
 
-    pub fn update(&mut self, ctx: &Context) {
-        if pressed(ctx, Control::B) || pressed(ctx, Control::Select) {
+    /// Returns the town the player chose to fly to.
+    pub fn ui(
+        &mut self,
+        app: &App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        state: &MapState,
+    ) -> Option<Location> {
+        if !self.alive {
+            return None;
+        }
+
+        if pressed(app, plugins, Control::B) {
             self.despawn();
+            return None;
         }
-        if pressed(ctx, Control::Up) {
-            self.selected.y = self.selected.y.saturating_sub(1);
+        if pressed(app, plugins, Control::Up) {
+            self.cursor.1 = self.cursor.1.saturating_sub(1);
         }
-        if pressed(ctx, Control::Down) {
-            self.selected.y = self.selected.y.wrapping_add(1);
+        if pressed(app, plugins, Control::Down) && self.cursor.1 + 1 < Self::HEIGHT {
+            self.cursor.1 += 1;
         }
-        if pressed(ctx, Control::Left) {
-            self.selected.x = self.selected.x.saturating_sub(1);
+        if pressed(app, plugins, Control::Left) {
+            self.cursor.0 = self.cursor.0.saturating_sub(1);
         }
-        if pressed(ctx, Control::Right) {
-            self.selected.x = self.selected.x.wrapping_add(1);
+        if pressed(app, plugins, Control::Right) && self.cursor.0 + 1 < Self::WIDTH {
+            self.cursor.0 += 1;
         }
-    }
 
-    pub fn draw(&self, ctx: &mut Context) {
-        for (pos, (icon, ..)) in self.locations.iter() {
-            let pos = *pos << Self::GUI_TILE_SIZE_MASK;
-            let (size, color) = match icon {
-                MapIconInit::City => (Vec2::one(), Color::RED),
-                MapIconInit::Route(s) => (*s, Color::rgb(0.5, 0.5, 0.5)),
-            };
-            let size = size << Self::GUI_TILE_SIZE_MASK;
-            draw_rectangle(ctx, pos.x as f32, pos.y as f32, size.x as f32, size.y as f32, color);
-        }
-        if let Some((_, name, _)) = self.locations.get(&self.selected) {
-            draw_text_left(ctx, &1, name, &Color::WHITE, 5.0, HEIGHT - 20.0);
+        let (x, y) = self.cursor;
+        let selected = self
+            .locations
+            .iter()
+            .find(|location| location.section.contains(x, y));
+        let destination = selected
+            .filter(|location| {
+                self.fly && location.town && state.places.visited.contains(&location.location)
+            })
+            .map(|location| location.location);
+
+        let mut cursor = None;
+        let mut close = false;
+        let mut fly = None;
+
+        egui::Window::new("Town Map")
+            .title_bar(false)
+            .show(egui, |ui| {
+                let (response, painter) = ui.allocate_painter(
+                    egui::vec2(
+                        Self::WIDTH as f32 * Self::TILE_SIZE,
+                        Self::HEIGHT as f32 * Self::TILE_SIZE,
+                    ),
+                    egui::Sense::click(),
+                );
+                let origin = response.rect.min;
+
+                painter.rect_filled(response.rect, 0.0, egui::Color32::from_rgb(48, 96, 160));
+
+                for location in self.locations.iter() {
+                    let color = match location.town {
+                        true => match state.places.visited.contains(&location.location) {
+                            true => egui::Color32::RED,
+                            false => egui::Color32::DARK_RED,
+                        },
+                        false => egui::Color32::GRAY,
+                    };
+                    painter.rect_filled(Self::rect(origin, &location.section), 0.0, color);
+                }
+
+                painter.rect_stroke(
+                    Self::rect(
+                        origin,
+                        &RegionSection {
+                            x,
+                            y,
+                            width: 1,
+                            height: 1,
+                        },
+                    ),
+                    0.0,
+                    egui::Stroke::new(1.0, egui::Color32::WHITE),
+                );
+
+                if response.clicked() {
+                    if let Some(pos) = response.interact_pointer_pos() {
+                        let pos = (pos - origin) / Self::TILE_SIZE;
+                        cursor = Some((pos.x as u8, pos.y as u8));
+                    }
+                }
+
+                ui.label(selected.map(|l| l.name.as_str()).unwrap_or_default());
+
+                if let Some(location) = destination {
+                    if ui.button("Fly").clicked() || pressed(app, plugins, Control::A) {
+                        fly = Some(location);
+                    }
+                }
+
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+
+        if let Some(cursor) = cursor {
+            self.cursor = cursor;
         }
-        let pos = self.selected << Self::GUI_TILE_SIZE_MASK;
-        draw_rectangle_lines(ctx, pos.x as f32, pos.y as f32, Self::GUI_TILE_SIZE, Self::GUI_TILE_SIZE, 1.0, Color::WHITE);
-    }
 
-    pub fn despawn_get(&mut self) -> Option<Location> {
-        let loc = self.locations.get(&self.selected).map(|(.., l)| *l);
-        if loc.is_some() {
+        if close || fly.is_some() {
             self.despawn();
         }
-        loc
+
+        fly
     }
 
-}
+    fn rect(origin: egui::Pos2, section: &RegionSection) -> egui::Rect {
+        egui::Rect::from_min_size(
+            origin + egui::vec2(section.x as f32, section.y as f32) * Self::TILE_SIZE,
+            egui::vec2(section.width as f32, section.height as f32) * Self::TILE_SIZE,
+        )
+    }
 
-impl Entity for WorldMapGui {
-    fn spawn(&mut self) {
+    /// Opens the map on the current section. Flying is only allowed if `fly` is true.
+    pub fn spawn(&mut self, fly: bool, current: Option<RegionSection>) {
         self.alive = true;
-        self.reset();
+        self.fly = fly;
+        self.cursor = current
+            .map(|section| (section.x, section.y))
+            .unwrap_or_default();
     }
 
-    fn despawn(&mut self) {
+    pub fn despawn(&mut self) {
         self.alive = false;
     }
 
-    fn alive(&self) -> bool {
+    pub fn alive(&self) -> bool {
         self.alive
     }
 }
-
-impl Reset for WorldMapGui {
-    fn reset(&mut self) {
-        self.selected = Default::default();
-    }
-}
\ No newline at end of file
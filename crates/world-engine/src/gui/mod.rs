@@ -1,4 +1,13 @@
 // mod text;
 // pub use text::TextWindow;
-// mod map;
-// pub use map::WorldMapGui;
+mod map;
+pub use map::WorldMapGui;
+
+mod badges;
+pub use badges::BadgeCaseGui;
+
+mod card;
+pub use card::TrainerCardGui;
+
+mod pc;
+pub use pc::PcGui;
@@ -0,0 +1,366 @@
+use rand::Rng;
+
+use crate::engine::{
+    controls::{pressed, Control},
+    egui, App, Plugins,
+};
+
+use worldlib::{
+    character::player::PlayerCharacter,
+    pokedex::{
+        item::Item,
+        moves::Move,
+        pokemon::{owned::SavedPokemon, Pokemon},
+        trainer::InitTrainer,
+        Dex,
+    },
+    state::storage::{BOX_SIZE, PARTY_SIZE, WALLPAPERS},
+};
+
+/// Bill's PC, used to move pokemon between the party and the storage boxes.
+#[derive(Default)]
+pub struct PcGui {
+    alive: bool,
+    selected: Option<Slot>,
+    /// Slot being moved, waiting for the player to pick where it goes
+    moving: Option<Slot>,
+    releasing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Party(usize),
+    Box(usize, usize),
+}
+
+enum PcAction {
+    Select(Slot),
+    Withdraw(usize, usize),
+    Deposit(usize),
+    Move(Slot),
+    Release(Slot),
+}
+
+impl PcGui {
+    const COLUMNS: usize = 6;
+
+    pub fn ui(
+        &mut self,
+        app: &App,
+        plugins: &mut Plugins,
+        egui: &egui::Context,
+        player: &mut PlayerCharacter,
+        trainer: &mut InitTrainer,
+        dex: (&Dex<Pokemon>, &Dex<Move>, &Dex<Item>),
+        random: &mut impl Rng,
+    ) {
+        if !self.alive {
+            return;
+        }
+
+        if pressed(app, plugins, Control::B) {
+            match self.moving.is_some() || self.releasing {
+                true => {
+                    self.moving = None;
+                    self.releasing = false;
+                }
+                false => self.despawn(),
+            }
+            return;
+        }
+
+        let pc = &mut player.pc;
+        let boxes = pc.boxes.len();
+        if boxes == 0 {
+            self.despawn();
+            return;
+        }
+        pc.current = pc.current.min(boxes - 1);
+        if pressed(app, plugins, Control::Left) {
+            pc.current = (pc.current + boxes - 1) % boxes;
+        }
+        if pressed(app, plugins, Control::Right) {
+            pc.current = (pc.current + 1) % boxes;
+        }
+
+        let mut action = None;
+        let mut close = false;
+
+        egui::Window::new("PC").title_bar(false).show(egui, |ui| {
+            let current = pc.current;
+
+            ui.horizontal(|ui| {
+                if ui.button("<").clicked() {
+                    pc.current = (current + boxes - 1) % boxes;
+                }
+                if let Some(b) = pc.boxes.get_mut(current) {
+                    ui.text_edit_singleline(&mut b.name);
+                }
+                if ui.button(">").clicked() {
+                    pc.current = (current + 1) % boxes;
+                }
+                if ui.button("Wallpaper").clicked() {
+                    if let Some(b) = pc.boxes.get_mut(current) {
+                        b.wallpaper = (b.wallpaper + 1) % WALLPAPERS;
+                    }
+                }
+            });
+
+            let wallpaper = pc
+                .boxes
+                .get(current)
+                .map(|b| b.wallpaper)
+                .unwrap_or_default();
+            egui::Frame::none()
+                .fill(Self::wallpaper(wallpaper))
+                .show(ui, |ui| {
+                    egui::Grid::new("pc_box").show(ui, |ui| {
+                        for index in 0..BOX_SIZE {
+                            let slot = Slot::Box(current, index);
+                            let name = pc
+                                .get(current, index)
+                                .map(|pokemon| Self::name(dex.0, pokemon))
+                                .unwrap_or_else(|| "-".to_owned());
+                            if ui
+                                .selectable_label(self.selected == Some(slot), name)
+                                .clicked()
+                            {
+                                action = Some(PcAction::Select(slot));
+                            }
+                            if (index + 1) % Self::COLUMNS == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                });
+
+            ui.separator();
+
+            for (index, pokemon) in trainer.party.iter().enumerate() {
+                let slot = Slot::Party(index);
+                if ui
+                    .selectable_label(
+                        self.selected == Some(slot),
+                        format!("{} Lv{}", pokemon.name(), pokemon.level),
+                    )
+                    .clicked()
+                {
+                    action = Some(PcAction::Select(slot));
+                }
+            }
+
+            ui.separator();
+
+            match (self.moving, self.releasing, self.selected) {
+                (Some(..), ..) => {
+                    ui.label("Move to where?");
+                }
+                (None, true, Some(slot)) => {
+                    ui.label("Release this pokemon?");
+                    ui.horizontal(|ui| {
+                        if ui.button("Yes").clicked() {
+                            action = Some(PcAction::Release(slot));
+                        }
+                        if ui.button("No").clicked() {
+                            self.releasing = false;
+                        }
+                    });
+                }
+                (None, .., selected) => {
+                    ui.horizontal(|ui| {
+                        match selected {
+                            Some(Slot::Box(index, slot)) if pc.get(index, slot).is_some() => {
+                                if ui.button("Withdraw").clicked() {
+                                    action = Some(PcAction::Withdraw(index, slot));
+                                }
+                            }
+                            Some(Slot::Party(index)) => {
+                                if ui.button("Deposit").clicked() {
+                                    action = Some(PcAction::Deposit(index));
+                                }
+                            }
+                            _ => (),
+                        }
+                        if let Some(slot) = selected {
+                            if ui.button("Move").clicked() {
+                                action = Some(PcAction::Move(slot));
+                            }
+                            if ui.button("Release").clicked() {
+                                self.releasing = true;
+                            }
+                        }
+                        if ui.button("Close").clicked() {
+                            close = true;
+                        }
+                    });
+                }
+            }
+        });
+
+        match action {
+            Some(PcAction::Select(slot)) => match self.moving.take() {
+                Some(from) => {
+                    Self::transfer(player, trainer, dex, random, from, slot);
+                    self.selected = None;
+                }
+                None => {
+                    self.selected = Some(slot);
+                    self.releasing = false;
+                }
+            },
+            Some(PcAction::Withdraw(index, slot)) => {
+                let len = trainer.party.len();
+                if len < PARTY_SIZE {
+                    if let Some((pokemon, ot)) = player.pc.take(index, slot) {
+                        Self::insert(player, trainer, dex, random, len, pokemon, ot);
+                    }
+                }
+                self.selected = None;
+            }
+            Some(PcAction::Deposit(index)) => {
+                if Self::removable(trainer, index) && !player.pc.is_full() {
+                    let pokemon = trainer.party.remove(index).uninit();
+                    let ot = player.original_trainers.remove(index);
+                    if let Err(pokemon) = player.pc.deposit(pokemon, ot) {
+                        Self::insert(player, trainer, dex, random, index, pokemon, ot);
+                    }
+                }
+                self.selected = None;
+            }
+            Some(PcAction::Move(slot)) => self.moving = Some(slot),
+            Some(PcAction::Release(slot)) => {
+                match slot {
+                    Slot::Box(index, slot) => {
+                        player.pc.take(index, slot);
+                    }
+                    Slot::Party(index) => {
+                        if Self::removable(trainer, index) {
+                            trainer.party.remove(index);
+                            player.original_trainers.remove(index);
+                        }
+                    }
+                }
+                self.releasing = false;
+                self.selected = None;
+            }
+            None => (),
+        }
+
+        if close {
+            self.despawn();
+        }
+    }
+
+    /// Moves a pokemon into another slot, swapping it with whatever was there.
+    fn transfer(
+        player: &mut PlayerCharacter,
+        trainer: &mut InitTrainer,
+        dex: (&Dex<Pokemon>, &Dex<Move>, &Dex<Item>),
+        random: &mut impl Rng,
+        from: Slot,
+        to: Slot,
+    ) {
+        match (from, to) {
+            (Slot::Box(a, b), Slot::Box(c, d)) => player.pc.swap((a, b), (c, d)),
+            (Slot::Party(a), Slot::Party(b)) => {
+                if a < trainer.party.len() && b < trainer.party.len() {
+                    trainer.party.swap(a, b);
+                    player.original_trainers.swap(a, b);
+                }
+            }
+            (Slot::Party(party), Slot::Box(index, slot))
+            | (Slot::Box(index, slot), Slot::Party(party)) => {
+                if party >= trainer.party.len() {
+                    return;
+                }
+                match player.pc.take(index, slot) {
+                    Some((stored, stored_ot)) => {
+                        let pokemon = trainer.party.remove(party).uninit();
+                        let ot = player.original_trainers.remove(party);
+                        if let Err(pokemon) = player.pc.put(index, slot, pokemon, ot) {
+                            Self::insert(player, trainer, dex, random, party, pokemon, ot);
+                        }
+                        Self::insert(player, trainer, dex, random, party, stored, stored_ot);
+                    }
+                    None => {
+                        if Self::removable(trainer, party) {
+                            let pokemon = trainer.party.remove(party).uninit();
+                            let ot = player.original_trainers.remove(party);
+                            if let Err(pokemon) = player.pc.put(index, slot, pokemon, ot) {
+                                Self::insert(player, trainer, dex, random, party, pokemon, ot);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the party member can leave the party,
+    /// which needs another pokemon that can still battle to stay behind.
+    fn removable(trainer: &InitTrainer, index: usize) -> bool {
+        index < trainer.party.len()
+            && trainer
+                .party
+                .iter()
+                .enumerate()
+                .any(|(i, pokemon)| i != index && !pokemon.fainted())
+    }
+
+    /// Puts a stored pokemon into the party, or back into the PC if it cannot be initialized.
+    fn insert(
+        player: &mut PlayerCharacter,
+        trainer: &mut InitTrainer,
+        dex: (&Dex<Pokemon>, &Dex<Move>, &Dex<Item>),
+        random: &mut impl Rng,
+        index: usize,
+        pokemon: SavedPokemon,
+        ot: Option<u16>,
+    ) {
+        let (pokedex, movedex, itemdex) = dex;
+        match pokemon.clone().init(random, pokedex, movedex, itemdex) {
+            Some(owned) => {
+                let index = index.min(trainer.party.len());
+                trainer.party.insert(index, owned);
+                player.original_trainers.insert(index, ot);
+            }
+            None => {
+                crate::engine::log::warn!("Could not initialize pokemon taken from the PC");
+                if player.pc.deposit(pokemon, ot).is_err() {
+                    crate::engine::log::error!("Lost pokemon as the PC is full");
+                }
+            }
+        }
+    }
+
+    fn name(pokedex: &Dex<Pokemon>, pokemon: &SavedPokemon) -> String {
+        match pokedex.try_get(&pokemon.pokemon) {
+            Some(p) => format!("{} Lv{}", p.name, pokemon.level),
+            None => format!("#{} Lv{}", pokemon.pokemon, pokemon.level),
+        }
+    }
+
+    fn wallpaper(wallpaper: u8) -> egui::Color32 {
+        let w = wallpaper as u16;
+        egui::Color32::from_rgb(
+            (80 + w * 53 % 160) as u8,
+            (80 + w * 97 % 160) as u8,
+            (80 + w * 29 % 160) as u8,
+        )
+    }
+
+    pub fn spawn(&mut self) {
+        self.alive = true;
+        self.selected = None;
+        self.moving = None;
+        self.releasing = false;
+    }
+
+    pub fn despawn(&mut self) {
+        self.alive = false;
+    }
+
+    pub fn alive(&self) -> bool {
+        self.alive
+    }
+}
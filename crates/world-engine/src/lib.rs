@@ -6,7 +6,7 @@ pub extern crate firecore_world as worldlib;
 pub use pokengine::engine;
 pub use pokengine::pokedex;
 
-mod gui;
+pub mod gui;
 pub mod map;
 
 pub fn color(message: worldlib::message::MessageColor) -> engine::graphics::Color {
@@ -1,5 +1,5 @@
 use pokengine::engine::{
-    graphics::{Color, Draw, DrawExt, DrawParams, Graphics, Texture},
+    graphics::{Color, Draw, DrawExt, DrawParams, DrawShapes, Graphics, Texture},
     math::Rect,
 };
 use worldlib::{
@@ -111,6 +111,15 @@ impl NpcTexture {
             let y = ((character.position.coords.y + camera.offset.y) << 4) as f32 - camera.focus.y
                 + character.offset.y
                 - (self.texture.height() - worldlib::TILE_SIZE);
+            if character.jumping != 0 {
+                draw.circle(worldlib::TILE_SIZE / 2.0)
+                    .position(
+                        x + self.data.width / 2.0,
+                        y + self.texture.height() - worldlib::TILE_SIZE / 2.0,
+                    )
+                    .color(Color::BLACK);
+            }
+            let y = y - character.jump_height();
             draw.texture(
                 &self.texture,
                 x,
@@ -2,7 +2,12 @@ use pokengine::engine::{
     graphics::{Color, Draw, DrawExt, DrawParams, Graphics},
     math::Rect,
 };
-use worldlib::{map::object::ObjectType, positions::Coordinate, state::map::MapState, TILE_SIZE};
+use worldlib::{
+    map::{object::ObjectType, WorldMap},
+    positions::Coordinate,
+    state::map::MapState,
+    TILE_SIZE,
+};
 
 use crate::engine::{graphics::Texture, HashMap};
 
@@ -52,65 +57,46 @@ impl ObjectTextures {
         }
     }
 
-    pub fn draw(&self, draw: &mut Draw, state: &MapState, camera: &CharacterCamera, color: Color) {
-        if let Some(state) = state.entities.get(&state.location) {
-            // for object in state.objects.values().filter(|object| !object.removed) {
-            //     if let Some(texture) = self.textures.get(&object.entity.data.group) {
-            //         let x = ((object.entity.coordinate.x + camera.offset.x) << 4) as f32
-            //             - camera.focus.x;
-            //         let y = ((object.entity.coordinate.y + camera.offset.y) << 4) as f32
-            //             - camera.focus.y;
-            //         draw.texture(
-            //             texture,
-            //             x,
-            //             y,
-            //             DrawParams {
-            //                 source: Some(Rect {
-            //                     x: 0.0,
-            //                     y: 0.0,
-            //                     width: TILE_SIZE,
-            //                     height: TILE_SIZE,
-            //                 }),
-            //                 color,
-            //                 ..Default::default()
-            //             },
-            //         );
-            //         // texture.draw(
-            //         //     ctx,
-            //         //     x,
-            //         //     y,
-            //         //     DrawParams {
-            //         //         source: Some(Rectangle {
-            //         //             x: 0.0,
-            //         //             y: 0.0,
-            //         //             w: TILE_SIZE,
-            //         //             h: TILE_SIZE,
-            //         //         }),
-            //         //         color,
-            //         //         ..Default::default()
-            //         //     },
-            //         // )
-            //     }
-            // }
-            // for object in state
-            //     .items
-            //     .values()
-            //     .filter(|object| !object.removed || !object.entity.data.hidden)
-            // {
-            //     /// "ball"
-            //     const BALL: &ObjectType =
-            //         unsafe { &ObjectType::from_bytes_unchecked(1819042146u32.to_ne_bytes()) };
-
-            //     if let Some(texture) = self.textures.get(BALL) {
-            //         let x = ((object.entity.coordinate.x + camera.offset.x) << 4) as f32
-            //             - camera.focus.x;
-            //         let y = ((object.entity.coordinate.y + camera.offset.y) << 4) as f32
-            //             - camera.focus.y;
-            //         draw.image(texture).position(x, y).color(color);
-            //     }
-            // }
+    pub fn draw(
+        &self,
+        draw: &mut Draw,
+        map: &WorldMap,
+        state: &MapState,
+        camera: &CharacterCamera,
+        color: Color,
+    ) {
+        let entities = state.entities.get(&map.id);
+        for (id, object) in map.objects.iter() {
+            if entities
+                .map(|entities| entities.is_removed(id))
+                .unwrap_or_default()
+            {
+                continue;
+            }
+            if let Some(texture) = object.group.as_ref().and_then(|group| self.textures.get(group)) {
+                let x = ((object.coordinate.x + camera.offset.x) << 4) as f32 - camera.focus.x;
+                let y = ((object.coordinate.y + camera.offset.y) << 4) as f32 - camera.focus.y;
+                draw.texture(
+                    texture,
+                    x,
+                    y,
+                    DrawParams {
+                        source: Some(Rect {
+                            x: 0.0,
+                            y: 0.0,
+                            width: TILE_SIZE,
+                            height: TILE_SIZE,
+                        }),
+                        color,
+                        ..Default::default()
+                    },
+                );
+            }
         }
+    }
 
+    /// Break animations only play on the current map.
+    pub fn draw_animations(&self, draw: &mut Draw, camera: &CharacterCamera, color: Color) {
         for anim in self.active.iter() {
             anim.draw(draw, camera, color);
         }
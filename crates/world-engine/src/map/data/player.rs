@@ -29,7 +29,7 @@ pub struct PlayerTexture {
     pub bush: bush::PlayerBushTexture,
 
     accumulator: f32,
-    jumping: bool,
+    hopping: bool,
     could_noclip: bool,
 }
 
@@ -75,12 +75,13 @@ impl PlayerTexture {
             textures,
             bush: bush::PlayerBushTexture::new(gfx)?,
             accumulator: 0.0,
-            jumping: false,
+            hopping: false,
             could_noclip: false,
         })
     }
 
-    pub fn jump(&mut self, player: &mut PlayerCharacter) {
+    /// Hop onto or off of water
+    pub fn hop(&mut self, player: &mut PlayerCharacter) {
         player.character.sprite = 0;
         self.could_noclip = player
             .character
@@ -91,20 +92,18 @@ impl PlayerTexture {
             .capabilities
             .remove(&CharacterState::NOCLIP);
         player.character.input_lock.increment();
-        for _ in 0..2 {
-            player
-                .character
-                .actions
-                .queue
-                .insert(0, ActionQueue::Move(player.character.position.direction));
-        }
+        player
+            .character
+            .actions
+            .queue
+            .insert(0, ActionQueue::Move(player.character.position.direction));
         self.accumulator = 0.0;
-        self.jumping = true;
+        self.hopping = true;
     }
 
     pub fn update(&mut self, delta: f32, character: &mut CharacterState) {
         self.bush.update(delta);
-        match self.jumping {
+        match self.hopping {
             false => {
                 if character.offset.is_zero() {
                     let texture = &self.textures[character.activity];
@@ -123,7 +122,7 @@ impl PlayerTexture {
                     if !self.could_noclip {
                         character.capabilities.remove(&CharacterState::NOCLIP);
                     }
-                    self.jumping = false;
+                    self.hopping = false;
                     self.accumulator = 0.0;
                 }
             }
@@ -135,7 +134,7 @@ impl PlayerTexture {
             let texture = &self.textures[character.activity];
             let screen_x = screen_x(draw.width());
             let screen_y = screen_y(draw.height());
-            if self.jumping {
+            if self.hopping || character.jumping != 0 {
                 draw.circle(TILE_SIZE / 2.0)
                     .position(screen_x, screen_y + 24.0)
                     .color(Color::BLACK);
@@ -144,22 +143,13 @@ impl PlayerTexture {
 
             let px = screen_x - width / 2.0;
 
-            let y = match self.jumping {
+            let y = match self.hopping {
                 true => {
-                    let negative = matches!(character.position.direction, Direction::Up);
-                    let o = character.offset.offset() / 4.0;
-                    match character.actions.queue.is_empty() {
-                        false => match negative {
-                            true => screen_y + o,
-                            false => screen_y - o,
-                        },
-                        true => match negative {
-                            true => screen_y + (TILE_SIZE / 4.0) - o,
-                            false => screen_y - (TILE_SIZE / 4.0) + o,
-                        },
-                    }
+                    const HALF_TILE_SIZE: f32 = TILE_SIZE / 2.0;
+                    let o = character.offset.offset().abs();
+                    screen_y - (HALF_TILE_SIZE - (o - HALF_TILE_SIZE).abs()) / 2.0
                 }
-                false => screen_y,
+                false => screen_y - character.jump_height(),
             };
 
             draw.texture(
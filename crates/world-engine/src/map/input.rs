@@ -1,7 +1,6 @@
 use crate::engine::controls::{down, pressed, Control};
 use pokengine::engine::notan::prelude::{App, Plugins};
 use worldlib::{
-    audio::MusicId,
     character::{player::PlayerCharacter, Activity, CharacterState},
     positions::Direction,
 };
@@ -17,12 +16,6 @@ impl PlayerInput {
     // pub const INPUT_LOCK: CharacterFlag = unsafe { CharacterFlag::from_bytes_unchecked(500186508905u64.to_ne_bytes()) };
     pub const INPUT_LOCK_VAL: i8 = 0;
 
-    pub const CYCLING_MUSIC: MusicId = unsafe {
-        MusicId::from_bytes_unchecked([
-            0x63, 0x79, 0x63, 0x6C, 0x69, 0x6E, 0x67, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        ])
-    };
-
     const MOVE_WAIT: f32 = 0.12;
 
     pub fn update(
@@ -50,25 +43,7 @@ impl PlayerInput {
                 }
             }
 
-            match pressed(app, plugins, Control::Select) {
-                true => match matches!(player.character.activity, Activity::Cycling) {
-                    false => {
-                        if player
-                            .character
-                            .capabilities
-                            .contains(&CharacterState::CYCLE)
-                        {
-                            player.character.activity = Activity::Cycling;
-                            pokengine::engine::music::play_music(app, plugins, &Self::CYCLING_MUSIC);
-                        }
-                    }
-                    true => {
-                        player.character.activity = Activity::Walking;
-                        try_run(app, plugins, player);
-                    }
-                },
-                false => try_run(app, plugins, player),
-            }
+            try_run(app, plugins, player);
 
             if down(app, plugins, Self::keybind(self.first_direction)) {
                 if self.wait > Self::MOVE_WAIT {
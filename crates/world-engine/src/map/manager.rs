@@ -2,8 +2,10 @@
 
 use crate::engine::{
     controls::{pressed, Control},
+    egui,
     graphics::{Color, Draw, DrawTextSection},
     gui::MessageBox,
+    log::warn,
     math::{ivec2, IVec2},
     music, sound,
     App, Plugins,
@@ -18,19 +20,26 @@ use worldlib::{
         manager::{InputEvent, WorldMapManager},
         movement::Elevation,
         warp::WarpDestination,
-        Brightness, WorldMap,
+        Brightness, MapKind, WorldMap,
     },
-    pokedex::trainer::{InitTrainer, Trainer},
+    pokedex::trainer::InitTrainer,
     positions::{Coordinate, Destination, Direction, Location, Spot},
     random::WorldRandoms,
     script::WorldScriptingEngine,
     state::{
+        clock::TimeOfDay,
         map::{MapEvent, MapState},
         WorldState,
     },
 };
 
-use crate::map::{data::ClientWorldData, input::PlayerInput, warp::WarpTransition};
+use crate::{
+    gui::{PcGui, WorldMapGui},
+    map::{
+        data::ClientWorldData, input::PlayerInput, warp::WarpTransition, weather::WeatherOverlay,
+    },
+    pokengine::gui::learn::LearnMoveGui,
+};
 
 pub mod npc;
 
@@ -43,6 +52,10 @@ pub struct WorldManager<S: WorldScriptingEngine> {
 
     pub warper: WarpTransition,
     pub input: PlayerInput,
+    pub town_map: WorldMapGui,
+    pub pc: PcGui,
+    pub learn: LearnMoveGui,
+    pub weather: WeatherOverlay,
 }
 
 impl<S: WorldScriptingEngine> WorldManager<S> {
@@ -51,11 +64,11 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
         self.world.get(location)
     }
 
-    pub fn start<R: Rng, P, B>(
+    pub fn start<R: Rng>(
         &mut self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
     ) {
         if state.map.location == Location::DEFAULT {
             let Spot { location, position } = self.world.data.spawn;
@@ -69,22 +82,31 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
                 },
             );
         }
+        if state.map.player.id.is_none() {
+            state.map.player.id = Some(randoms.general.gen());
+        }
         self.world.on_warp(state, randoms, trainer);
     }
 
-    pub fn post_battle(&mut self, state: &mut MapState, trainer: &mut InitTrainer, winner: bool) {
-        self.world.data.post_battle(state, trainer, winner)
+    pub fn post_battle(
+        &mut self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        winner: bool,
+        participants: &[usize],
+    ) {
+        self.world.post_battle(state, trainer, winner, participants)
     }
 
     pub fn spawn(&self) -> Spot {
         self.world.data.spawn
     }
 
-    pub fn try_teleport<R: Rng, P, B>(
+    pub fn try_teleport<R: Rng>(
         &mut self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
         location: Location,
     ) -> bool {
         if self.world.contains(&location) {
@@ -95,8 +117,8 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
         }
     }
 
-    pub fn update_capabilities(&self, character: &mut worldlib::character::CharacterState, trainer: &mut InitTrainer) {
-        self.world.update_capabilities(character, trainer);
+    pub fn update_capabilities(&self, player: &mut PlayerCharacter, trainer: &mut InitTrainer) {
+        self.world.update_capabilities(player, trainer);
     }
 
     pub fn update<R: Rng>(
@@ -117,6 +139,7 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
         //     }
 
         self.data.update(delta, &mut state.map.player.character);
+        self.weather.update(delta);
 
         if self.warper.alive() {
             if let Some(music) = self.warper.update(&self.world.data, &mut state.map, delta) {
@@ -139,11 +162,47 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
             self.world.input(&mut state.map, InputEvent::Interact);
         }
 
+        if pressed(app, plugins, Control::Select)
+            && !state.map.player.character.moving()
+            && !state.map.player.character.input_lock.active()
+        {
+            self.world.input(&mut state.map, InputEvent::Bicycle);
+        }
+
         self.world.update(state, trainer, randoms, delta);
 
         for action in std::mem::take(&mut state.map.events) {
             match action {
-                MapEvent::PlayerJump => self.data.player.jump(&mut state.map.player),
+                MapEvent::PlayerHop => self.data.player.hop(&mut state.map.player),
+                MapEvent::BreakObject(coordinate, group) => {
+                    self.data.object.add(coordinate, &group)
+                }
+                MapEvent::OpenTownMap(fly) => {
+                    let current = self
+                        .world
+                        .get(&state.map.location)
+                        .and_then(|map| map.settings.region);
+                    self.town_map.spawn(fly, current);
+                    state.map.player.character.input_lock.increment();
+                }
+                MapEvent::OpenPc => {
+                    self.pc.spawn();
+                    state.map.player.character.input_lock.increment();
+                }
+                MapEvent::GivePokemon(pokemon) => {
+                    self.world
+                        .give_pokemon(&mut state.map, trainer, &mut randoms.general, pokemon)
+                }
+                MapEvent::LearnMove(pokemon, id) => match self.world.movedex.try_get(&id) {
+                    Some(m) => {
+                        if !self.learn.alive() {
+                            state.map.player.character.input_lock.increment();
+                        }
+                        self.learn.spawn(pokemon, m.clone());
+                    }
+                    None => warn!("Could not find move {} to learn", id),
+                },
+                MapEvent::Fish(rod) => self.world.fish(&mut state.map, trainer, randoms, rod),
                 MapEvent::PlayMusic(music) => match music {
                     Some(music) => match music::get_current_music(plugins) {
                         Some(playing) => {
@@ -177,11 +236,11 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
         }
     }
 
-    pub fn teleport<R: Rng, P, B>(
+    pub fn teleport<R: Rng>(
         &mut self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
         location: Location,
     ) {
         if let Some(map) = self.world.data.maps.get(&location) {
@@ -223,14 +282,67 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
         }
     }
 
-    pub fn ui(
+    pub fn ui<R: Rng>(
         &mut self,
         app: &App,
         plugins: &mut Plugins,
-        egui: &crate::engine::notan::egui::Context,
+        egui: &egui::Context,
         state: &mut MapState,
+        randoms: &mut WorldRandoms<R>,
+        trainer: &mut InitTrainer,
     ) {
         MessageBox::ui(app, plugins, egui, &mut state.message);
+        if self.town_map.alive() {
+            if let Some(location) = self.town_map.ui(app, plugins, egui, state) {
+                self.world.fly(state, &location);
+            }
+            if !self.town_map.alive() {
+                state.player.character.input_lock.decrement();
+            }
+        }
+        if self.pc.alive() {
+            let dex = (
+                self.world.pokedex.as_ref(),
+                self.world.movedex.as_ref(),
+                self.world.itemdex.as_ref(),
+            );
+            self.pc.ui(
+                app,
+                plugins,
+                egui,
+                &mut state.player,
+                trainer,
+                dex,
+                &mut randoms.general,
+            );
+            if !self.pc.alive() {
+                state.player.character.input_lock.decrement();
+            }
+        }
+        if self.learn.alive() {
+            self.learn.ui(app, plugins, egui, &mut trainer.party);
+            if !self.learn.alive() {
+                state.player.character.input_lock.decrement();
+            }
+        }
+        if let Some(prompt) = state.prompt.as_mut() {
+            if state
+                .message
+                .as_ref()
+                .map(|message| message.page() + 1 >= message.pages() && message.waiting())
+                .unwrap_or(true)
+            {
+                egui::Window::new("Prompt")
+                    .title_bar(false)
+                    .show(egui, |ui| {
+                        if ui.button("Yes").clicked() || pressed(app, plugins, Control::A) {
+                            prompt.answer = Some(true);
+                        } else if ui.button("No").clicked() || pressed(app, plugins, Control::B) {
+                            prompt.answer = Some(false);
+                        }
+                    });
+            }
+        }
     }
 
     pub fn draw(&self, draw: &mut Draw, state: &MapState) {
@@ -243,6 +355,18 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
                     Brightness::Night => Color::new(0.6, 0.6, 0.6, 1.0),
                 };
 
+                let color = match current.settings.kind {
+                    MapKind::Outdoor => {
+                        let tint = match state.clock.time_of_day() {
+                            TimeOfDay::Morning => Color::new(1.0, 0.92, 0.85, 1.0),
+                            TimeOfDay::Day => Color::WHITE,
+                            TimeOfDay::Night => Color::new(0.5, 0.55, 0.8, 1.0),
+                        };
+                        Color::new(color.r * tint.r, color.g * tint.g, color.b * tint.b, color.a)
+                    }
+                    _ => color,
+                };
+
                 super::draw(draw, current, state, &self.data, &camera, true, color);
 
                 match &current.chunk {
@@ -352,9 +476,13 @@ impl<S: WorldScriptingEngine> WorldManager<S> {
             self.warper.draw_door(draw, &self.data.tiles, &camera);
         }
 
+        self.data.object.draw_animations(draw, &camera, color);
         self.data.player.draw(draw, &state.player.character, color);
         if !state.debug_mode {
             self.data.player.bush.draw(draw, &camera);
+            if let Some(map) = self.world.get(&state.location) {
+                self.weather.draw(draw, map.settings.weather);
+            }
             self.warper.draw(draw);
         }
     }
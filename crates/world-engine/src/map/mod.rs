@@ -11,6 +11,7 @@ pub mod manager;
 
 pub mod data;
 pub mod warp;
+pub mod weather;
 
 mod camera;
 pub use camera::CharacterCamera;
@@ -124,7 +125,7 @@ pub fn draw(
         data.npc.draw(draw, &character, camera, color);
     }
 
-    data.object.draw(draw, state, camera, color);
+    data.object.draw(draw, map, state, camera, color);
     // for script in map.scripts.iter() {
     //     if script.alive() {
     //         if let Some(action) = script.actions.front() {
@@ -0,0 +1,87 @@
+use worldlib::map::Weather;
+
+use crate::engine::graphics::{Color, Draw, DrawShapes};
+
+/// Draws weather effects over the map.
+#[derive(Default)]
+pub struct WeatherOverlay {
+    accumulator: f32,
+}
+
+impl WeatherOverlay {
+    const PARTICLES: u32 = 48;
+    const LOOP: f32 = 3600.0;
+    const LIGHTNING: f32 = 8.0;
+
+    pub fn update(&mut self, delta: f32) {
+        self.accumulator = (self.accumulator + delta) % Self::LOOP;
+    }
+
+    pub fn draw(&self, draw: &mut Draw, weather: Weather) {
+        match weather {
+            Weather::Clear | Weather::Shade => (),
+            Weather::Sunny => Self::fill(draw, Color::new(1.0, 0.9, 0.5, 0.12)),
+            Weather::Rain => self.rain(draw, 1.0),
+            Weather::Thunderstorm => {
+                self.rain(draw, 1.5);
+                if self.accumulator % Self::LIGHTNING < 0.15 {
+                    Self::fill(draw, Color::new(1.0, 1.0, 1.0, 0.5));
+                }
+            }
+            Weather::Fog => Self::fill(draw, Color::new(0.85, 0.85, 0.9, 0.4)),
+            Weather::Ash => self.particles(
+                draw,
+                (8.0, 24.0),
+                (2.0, 2.0),
+                Color::new(0.4, 0.4, 0.4, 0.8),
+            ),
+            Weather::Sandstorm => {
+                Self::fill(draw, Color::new(0.85, 0.7, 0.45, 0.25));
+                self.particles(
+                    draw,
+                    (-160.0, 16.0),
+                    (3.0, 1.0),
+                    Color::new(0.8, 0.65, 0.4, 0.8),
+                );
+            }
+        }
+    }
+
+    fn rain(&self, draw: &mut Draw, speed: f32) {
+        let (width, height) = (draw.width(), draw.height());
+        let color = Color::new(0.6, 0.7, 0.9, 0.7);
+        for (x, y) in self.positions(width, height, (-60.0 * speed, 240.0 * speed)) {
+            draw.line((x, y), (x - 2.0, y + 8.0))
+                .width(1.0)
+                .color(color);
+        }
+    }
+
+    fn particles(&self, draw: &mut Draw, speed: (f32, f32), size: (f32, f32), color: Color) {
+        let (width, height) = (draw.width(), draw.height());
+        for (x, y) in self.positions(width, height, speed) {
+            draw.rect((x, y), size).color(color);
+        }
+    }
+
+    fn positions(
+        &self,
+        width: f32,
+        height: f32,
+        speed: (f32, f32),
+    ) -> impl Iterator<Item = (f32, f32)> {
+        let time = self.accumulator;
+        (0..Self::PARTICLES).map(move |i| {
+            // spread the particles out with a cheap hash
+            let seed = i.wrapping_mul(2654435761);
+            let x = (seed % 997) as f32 / 997.0 * width + time * speed.0;
+            let y = ((seed >> 10) % 991) as f32 / 991.0 * height + time * speed.1;
+            (x.rem_euclid(width), y.rem_euclid(height))
+        })
+    }
+
+    fn fill(draw: &mut Draw, color: Color) {
+        draw.rect((0.0, 0.0), (draw.width(), draw.height()))
+            .color(color);
+    }
+}
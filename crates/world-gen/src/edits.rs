@@ -76,6 +76,7 @@ impl Edits {
                     match warp {
                         WarpEdits::Add(area, dest) => map.warps.push(WarpEntry {
                             area: area.into(),
+                            elevation: Default::default(),
                             destination: WarpDestination {
                                 location: dest.location.into(),
                                 position: dest.position,
@@ -1,23 +1,26 @@
 use std::{num::ParseIntError, sync::Arc};
 
 use dashmap::DashMap;
-use hashbrown::{hash_map::DefaultHashBuilder as RandomState, HashMap};
+use hashbrown::{hash_map::DefaultHashBuilder as RandomState, HashMap, HashSet};
 
 use bin::BinaryMap;
 
 use firecore_world::{
     character::npc::{
         group::TrainerGroupId,
-        trainer::{NpcTrainer, TrainerDisable},
+        trainer::{BadgeId, NpcTrainer, TrainerDisable},
         Npc, NpcMovement, Npcs,
     },
     map::{
         chunk::{ChunkConnections, Connection, WorldChunk},
+        data::tile::{PaletteData, PaletteDataMap},
+        machine::MachineData,
         movement::Elevation,
         object::*,
         warp::{WarpDestination, WarpEntry},
         wild::{WildEntry, WildType},
-        Brightness, PaletteId, WorldMap, WorldMapSettings, WorldTile,
+        Brightness, MapKind, PaletteId, RegionSection, TileId, Weather, WorldMap, WorldMapSettings,
+        WorldTile,
     },
     pokedex::{
         item::Item,
@@ -48,6 +51,7 @@ use crate::{
 const PATH: &str = "http://raw.githubusercontent.com/pret/pokefirered/master";
 
 mod edits;
+mod machine;
 mod map;
 mod mapping;
 
@@ -79,11 +83,22 @@ pub struct ParsedData {
     pub messages: Messages,
     pub trainers: Trainers,
     pub parties: Parties,
+    pub sections: HashMap<String, RegionSection>,
+    pub heal_locations: HashMap<String, Coordinate>,
+    /// Metatile behaviour names and their values
+    pub behaviours: HashMap<String, u16>,
+    /// Behaviours of every metatile in a tileset
+    pub tilesets: HashMap<String, Vec<u16>>,
+    /// TMs and HMs and the species that can learn them
+    pub machines: MachineData,
 }
 
 pub struct WorldData {
     pub maps: HashMap<Location, WorldMap>,
     pub scripts: DefaultWorldScriptEngine,
+    /// Tile behaviours imported from metatile attributes
+    pub palettes: PaletteDataMap,
+    pub machines: MachineData,
 }
 
 pub fn compile(
@@ -161,11 +176,25 @@ pub fn compile(
     //     &data.messages,
     // );
 
+    println!("Converting metatile behaviours...");
+
+    let palettes = into_palette_data(&mappings, &data);
+
+    let machines = std::mem::take(&mut data.machines);
+
     println!("Done!");
 
     Ok(WorldData {
         maps: new_maps.into_par_iter().collect(),
-        scripts: create_world_script_data(&mappings, &data.scripts, &data.messages, &locations),
+        scripts: create_world_script_data(
+            &mappings,
+            &data.pokedex,
+            &data.scripts,
+            &data.messages,
+            &locations,
+        ),
+        palettes,
+        machines,
     })
 }
 
@@ -219,6 +248,13 @@ pub fn create_data() -> anyhow::Result<ParsedData> {
         .text_utf8()?;
     let parties = script::trainer::party::parse_parties(&parties)?;
 
+    println!("Getting TM and HM learnsets...");
+
+    let machines = attohttpc::get(format!("{}/src/data/pokemon/tmhm_learnsets.h", PATH))
+        .send()?
+        .text_utf8()?;
+    let machines = machine::parse_machines(&machines, &pokedex, &movedex, &itemdex);
+
     println!("Getting layouts...");
 
     let layouts = attohttpc::get(format!("{}/data/layouts/layouts.json", PATH))
@@ -237,6 +273,39 @@ pub fn create_data() -> anyhow::Result<ParsedData> {
         .send()?
         .json::<JsonWildEncounters>()?;
 
+    println!("Getting region map sections...");
+
+    let sections = attohttpc::get(format!(
+        "{}/src/data/region_map/region_map_sections.json",
+        PATH
+    ))
+    .send()?
+    .json::<map::JsonRegionMapSections>()?
+    .map_sections
+    .into_iter()
+    .flat_map(|section| {
+        Some((
+            section.id,
+            RegionSection {
+                x: section.x?,
+                y: section.y?,
+                width: section.width?,
+                height: section.height?,
+            },
+        ))
+    })
+    .collect();
+
+    println!("Getting heal locations...");
+
+    let heal_locations = attohttpc::get(format!("{}/src/data/heal_locations.json", PATH))
+        .send()?
+        .json::<map::JsonHealLocations>()?
+        .heal_locations
+        .into_iter()
+        .map(|heal| (heal.map, Coordinate::new(heal.x, heal.y)))
+        .collect();
+
     println!("Parsing map groups...");
 
     let maps = serde_json::from_slice::<Value>(&maps)?;
@@ -272,6 +341,55 @@ pub fn create_data() -> anyhow::Result<ParsedData> {
         .map(|l| (l.id.clone(), l))
         .collect::<DashMap<String, JsonMapLayout, RandomState>>();
 
+    println!("Getting metatile behaviours...");
+
+    let behaviours = attohttpc::get(format!("{}/include/constants/metatile_behaviors.h", PATH))
+        .send()?
+        .text_utf8()?;
+    let behaviours = parse_behaviours(&behaviours);
+
+    println!("Getting metatile attributes...");
+
+    let tilesets = layouts
+        .iter()
+        .flat_map(|layout| {
+            [
+                ("primary", layout.primary_tileset.clone()),
+                ("secondary", layout.secondary_tileset.clone()),
+            ]
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .flat_map(|(kind, tileset)| {
+            let path = format!(
+                "{}/data/tilesets/{}/{}/metatile_attributes.bin",
+                PATH,
+                kind,
+                tileset_folder(&tileset)
+            );
+            match attohttpc::get(path)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.bytes())
+            {
+                Ok(bytes) => Some((
+                    tileset,
+                    bytes
+                        .chunks_exact(4)
+                        .map(|a| (u32::from_le_bytes([a[0], a[1], a[2], a[3]]) & 0x1FF) as u16)
+                        .collect(),
+                )),
+                Err(err) => {
+                    eprintln!(
+                        "Could not get metatile attributes for {} with error {}",
+                        tileset, err
+                    );
+                    None
+                }
+            }
+        })
+        .collect();
+
     names.into_par_iter().for_each(|map| {
         let path = format!("{}/data/maps/{}/map.json", PATH, map);
         let scripts_path = format!("{}/data/maps/{}/scripts.inc", PATH, map);
@@ -351,6 +469,11 @@ pub fn create_data() -> anyhow::Result<ParsedData> {
         messages,
         trainers,
         parties,
+        sections,
+        heal_locations,
+        behaviours,
+        tilesets,
+        machines,
     };
 
     println!("Done parsing maps!");
@@ -416,7 +539,15 @@ fn into_world_map(
         .data
         .coord_events
         .par_iter()
-        .map(|e| (Coordinate { x: e.x, y: e.y }, e.script.clone()))
+        .map(|e| {
+            (
+                Coordinate3d {
+                    xy: Coordinate { x: e.x, y: e.y },
+                    z: Elevation(e.elevation),
+                },
+                e.script.clone(),
+            )
+        })
         .collect();
 
     Some((
@@ -460,21 +591,38 @@ fn into_world_map(
                 .collect(),
             wild: encounters.remove(&map.data.id).map(|(.., v)| v).flatten(),
             npcs,
-            // objects: into_world_objects(mappings, &map.data.object_events),
+            objects: into_world_objects(mappings, &map.data.object_events),
+            pcs: map
+                .data
+                .bg_events
+                .iter()
+                .filter(|event| {
+                    event.type_ == "sign" && event.script.as_deref() == Some("EventScript_PC")
+                })
+                .map(|event| Coordinate {
+                    x: event.x as _,
+                    y: event.y as _,
+                })
+                .collect(),
             // items: into_world_items(data, &map.data.bg_events),
             // signs: into_world_signs(data, &map.data.bg_events),
             settings: WorldMapSettings {
-                fly_position: None,
+                fly_position: data.heal_locations.get(&map.data.id).copied(),
                 brightness: match map.data.weather == "WEATHER_SHADE" {
                     true => Brightness::Night,
                     false => Brightness::Day,
                 },
+                weather: into_weather(&map.data.weather),
                 transition: mappings
                     .map
                     .transition
                     .get(&map.data.battle_scene)
                     .copied()
                     .unwrap_or_else(|| WorldMapSettings::default_transition()),
+                kind: into_map_kind(&map.data.map_type, map.data.allow_escaping),
+                region: data.sections.get(&map.data.region_map_section).copied(),
+                cycling: map.data.allow_cycling,
+                force_cycling: mappings.map.force_cycling.contains(&map.data.id),
             },
             // scripts: Default::default(),
         },
@@ -485,8 +633,139 @@ fn into_world_map(
     ))
 }
 
+/// Parses metatile behaviour constants, which are either defines or an enum.
+fn parse_behaviours(header: &str) -> HashMap<String, u16> {
+    let mut behaviours = HashMap::new();
+    let mut next = 0;
+    for line in header.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let mut words = line
+            .trim_start_matches("#define")
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '=')
+            .filter(|word| !word.is_empty());
+        if let Some(name) = words.next().filter(|name| name.starts_with("MB_")) {
+            let value = words
+                .next()
+                .and_then(|value| match value.strip_prefix("0x") {
+                    Some(hex) => u16::from_str_radix(hex, 16).ok(),
+                    None => value.parse().ok(),
+                })
+                .unwrap_or(next);
+            behaviours.insert(name.to_owned(), value);
+            next = value + 1;
+        }
+    }
+    behaviours
+}
+
+/// "gTileset_PalletTown" -> "pallet_town"
+fn tileset_folder(tileset: &str) -> String {
+    let name = tileset.trim_start_matches("gTileset_");
+    let mut folder = String::with_capacity(name.len() + 4);
+    for (i, c) in name.char_indices() {
+        if c.is_ascii_uppercase() && i != 0 {
+            folder.push('_');
+        }
+        folder.push(c.to_ascii_lowercase());
+    }
+    folder
+}
+
+fn into_palette_data(mappings: &NameMappings, data: &ParsedData) -> PaletteDataMap {
+    const SPINNERS: [(&str, Direction); 4] = [
+        ("MB_SPIN_UP", Direction::Up),
+        ("MB_SPIN_DOWN", Direction::Down),
+        ("MB_SPIN_LEFT", Direction::Left),
+        ("MB_SPIN_RIGHT", Direction::Right),
+    ];
+    const CURRENTS: [(&str, Direction); 4] = [
+        ("MB_NORTHWARD_CURRENT", Direction::Up),
+        ("MB_SOUTHWARD_CURRENT", Direction::Down),
+        ("MB_WESTWARD_CURRENT", Direction::Left),
+        ("MB_EASTWARD_CURRENT", Direction::Right),
+    ];
+
+    let mut palettes = PaletteDataMap::new();
+
+    for (tileset, tiles) in data.tilesets.iter() {
+        let id = match mappings
+            .palettes
+            .primary
+            .get(tileset)
+            .or_else(|| mappings.palettes.secondary.get(tileset))
+        {
+            Some(id) => *id,
+            None => continue,
+        };
+
+        let mut palette = PaletteData::default();
+
+        for (tile, behaviour) in tiles.iter().enumerate() {
+            let tile = tile as TileId;
+            let is = |name: &str| data.behaviours.get(name) == Some(behaviour);
+            if is("MB_ICE") {
+                palette.ice.push(tile);
+            } else if is("MB_STOP_SPINNING") {
+                palette.stops.push(tile);
+            } else if let Some((.., direction)) = SPINNERS.iter().find(|(name, ..)| is(name)) {
+                palette.spinners.entry(*direction).or_default().push(tile);
+            } else if is("MB_CYCLING_ROAD_PULL_DOWN") || is("MB_CYCLING_ROAD_PULL_DOWN_GRASS") {
+                palette.slopes.push(tile);
+            } else if let Some((.., direction)) = CURRENTS.iter().find(|(name, ..)| is(name)) {
+                palette.currents.entry(*direction).or_default().push(tile);
+            }
+        }
+
+        palettes.insert(id, palette);
+    }
+
+    palettes
+}
+
+fn into_weather(weather: &str) -> Weather {
+    match weather {
+        "WEATHER_SUNNY" | "WEATHER_SUNNY_CLOUDS" | "WEATHER_DROUGHT" => Weather::Sunny,
+        "WEATHER_RAIN" | "WEATHER_DOWNPOUR" => Weather::Rain,
+        "WEATHER_RAIN_THUNDERSTORM" => Weather::Thunderstorm,
+        "WEATHER_FOG_HORIZONTAL" | "WEATHER_FOG_DIAGONAL" => Weather::Fog,
+        "WEATHER_VOLCANIC_ASH" => Weather::Ash,
+        "WEATHER_SHADE" => Weather::Shade,
+        "WEATHER_SANDSTORM" => Weather::Sandstorm,
+        _ => Weather::Clear,
+    }
+}
+
+/// The badge each Kanto gym leader gives when beaten in their gym.
+fn into_badge(trainer: &str) -> Option<BadgeId> {
+    let badge = match trainer {
+        "TRAINER_LEADER_BROCK" => "boulder",
+        "TRAINER_LEADER_MISTY" => "cascade",
+        "TRAINER_LEADER_LT_SURGE" => "thunder",
+        "TRAINER_LEADER_ERIKA" => "rainbow",
+        "TRAINER_LEADER_KOGA" => "soul",
+        "TRAINER_LEADER_SABRINA" => "marsh",
+        "TRAINER_LEADER_BLAINE" => "volcano",
+        "TRAINER_LEADER_GIOVANNI" => "earth",
+        _ => return None,
+    };
+    badge.parse().ok()
+}
+
+fn into_map_kind(map_type: &str, allow_escaping: bool) -> MapKind {
+    match allow_escaping {
+        true => MapKind::Dungeon,
+        false => match map_type {
+            "MAP_TYPE_TOWN" | "MAP_TYPE_CITY" | "MAP_TYPE_ROUTE" | "MAP_TYPE_OCEAN_ROUTE" => {
+                MapKind::Outdoor
+            }
+            _ => MapKind::Indoor,
+        },
+    }
+}
+
 fn create_world_script_data(
     mappings: &NameMappings,
+    pokedex: &Dex<Pokemon>,
     scripts: &Scripts,
     messages: &Messages,
     locations: &DashMap<Location, ScriptLocation>,
@@ -501,7 +780,7 @@ fn create_world_script_data(
                 let args: Vec<_> = v
                     .commands
                     .iter()
-                    .map(|c| match into_instruction(mappings, &k, c) {
+                    .map(|c| match into_instruction(mappings, pokedex, &k, c) {
                         Ok(i) => Some(i),
                         Err(err) => match err {
                             InstructionError::Unknown(..) => None,
@@ -529,6 +808,7 @@ fn create_world_script_data(
 
 fn into_instruction(
     mappings: &crate::NameMappings,
+    pokedex: &Dex<Pokemon>,
     id: &ScriptId,
     command: &Command,
 ) -> Result<WorldInstruction, InstructionError> {
@@ -586,6 +866,10 @@ fn into_instruction(
         "walk_up" => WorldInstruction::Walk(Direction::Up),
         "walk_left" => WorldInstruction::Walk(Direction::Left),
         "walk_right" => WorldInstruction::Walk(Direction::Right),
+        "jump_2_down" => WorldInstruction::Jump(Direction::Down),
+        "jump_2_up" => WorldInstruction::Jump(Direction::Up),
+        "jump_2_left" => WorldInstruction::Jump(Direction::Left),
+        "jump_2_right" => WorldInstruction::Jump(Direction::Right),
         "walk_in_place_fastest_up" => WorldInstruction::Look(Direction::Up),
         // Singular trainer battle
         "trainerbattle_single" => WorldInstruction::TrainerBattleSingle,
@@ -622,6 +906,19 @@ fn into_instruction(
             })?,
             command.arguments[1].clone(),
         ),
+        // Pokemon
+        "givemon" => {
+            let name = command.arguments[0][8..].replace('_', "-");
+            let pokemon = pokedex.try_get_named(&name).ok_or_else(|| {
+                InstructionError::Unknown(id.clone(), command.arguments[0].clone())
+            })?;
+            WorldInstruction::GivePokemon(
+                pokemon.id,
+                command.arguments[1].parse().map_err(|err| {
+                    InstructionError::ParseInt(id.clone(), command.arguments[1].clone(), err)
+                })?,
+            )
+        }
         com => return Err(InstructionError::Unknown(id.to_string(), com.to_owned())),
     })
 }
@@ -715,6 +1012,7 @@ fn into_world_warp(
                 y: warp.y as _,
             },
         },
+        elevation: Elevation(warp.elevation),
         destination: WarpDestination {
             location: destination,
             position: {
@@ -882,7 +1180,7 @@ fn into_world_npcs(
                                 },
                                 encounter: data.messages.get(encounter_id).unwrap().clone(),
                                 defeat: data.messages.get(defeat_id).unwrap().clone(),
-                                badge: None,
+                                badge: into_badge(id),
                                 disable: TrainerDisable::DisableSelf,
                             });
 
@@ -965,29 +1263,30 @@ fn into_world_npcs(
     )
 }
 
-// fn into_world_objects(mappings: &NameMappings, events: &[JsonObjectEvent]) -> Objects {
-//     events
-//         .par_iter()
-//         .enumerate()
-//         .flat_map(
-//             |(index, event)| match mappings.objects.objects.get(&event.graphics_id) {
-//                 Some(id) => Some({
-//                     (
-//                         index as _,
-//                         ObjectEntity {
-//                             data: ObjectEntityData { group: *id },
-//                             coordinate: Coordinate {
-//                                 x: event.x as _,
-//                                 y: event.y as _,
-//                             },
-//                         },
-//                     )
-//                 }),
-//                 None => None,
-//             },
-//         )
-//         .collect()
-// }
+fn into_world_objects(mappings: &NameMappings, events: &[JsonObjectEvent]) -> Objects {
+    events
+        .par_iter()
+        .enumerate()
+        .flat_map(|(index, event)| {
+            mappings
+                .objects
+                .objects
+                .get(&event.graphics_id)
+                .map(|group| {
+                    (
+                        index as ObjectId,
+                        MapObject {
+                            coordinate: Coordinate {
+                                x: event.x as _,
+                                y: event.y as _,
+                            },
+                            group: Some(*group),
+                        },
+                    )
+                })
+        })
+        .collect()
+}
 
 // fn into_world_items(data: &ParsedData, events: &[JsonBgEvent]) -> Items {
 //     events
@@ -0,0 +1,68 @@
+use hashbrown::HashMap;
+
+use firecore_world::{
+    map::machine::{Machine, MachineData},
+    pokedex::{item::Item, moves::Move, pokemon::Pokemon, Dex},
+};
+
+/// Reads which species can learn the move in each TM and HM from `tmhm_learnsets.h`
+pub fn parse_machines(
+    file: &str,
+    pokedex: &Dex<Pokemon>,
+    movedex: &Dex<Move>,
+    itemdex: &Dex<Item>,
+) -> MachineData {
+    let mut machines = MachineData::new();
+    let mut ids = HashMap::new();
+    let mut species = None;
+
+    for line in file.lines() {
+        if let Some(start) = line.find("[SPECIES_") {
+            species = line[start + 9..]
+                .split_once(']')
+                .and_then(|(name, ..)| pokedex.try_get_named(&name.replace('_', "-")))
+                .map(|pokemon| pokemon.id);
+        }
+
+        let species = match species {
+            Some(species) => species,
+            None => continue,
+        };
+
+        // TMHM(TM06_TOXIC)
+        for machine in line.split("TMHM(").skip(1) {
+            let machine = match machine.split_once(')') {
+                Some((machine, ..)) => machine,
+                None => continue,
+            };
+
+            let ids = ids.entry(machine).or_insert_with(|| {
+                let (number, name) = machine.split_once('_')?;
+                let item = itemdex.try_get_named(number).or_else(|| {
+                    println!("Cannot get item id {}", number);
+                    None
+                })?;
+                let name = name.replace('_', " ");
+                let teaches = movedex.try_get_named(&name).or_else(|| {
+                    println!("Cannot get move id {}", name);
+                    None
+                })?;
+                Some((item.id, teaches.id, number.starts_with("HM")))
+            });
+
+            if let Some((item, teaches, reusable)) = *ids {
+                machines
+                    .entry(item)
+                    .or_insert_with(|| Machine {
+                        teaches,
+                        pokemon: Default::default(),
+                        reusable,
+                    })
+                    .pokemon
+                    .insert(species);
+            }
+        }
+    }
+
+    machines
+}
@@ -69,3 +69,30 @@ pub struct LayoutOrNone {
 
 #[derive(Debug, Deserialize)]
 pub struct Nothing {}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonRegionMapSections {
+    pub map_sections: Vec<JsonRegionMapSection>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonRegionMapSection {
+    pub id: String,
+    /// Sections outside of the region map have no position
+    pub x: Option<u8>,
+    pub y: Option<u8>,
+    pub width: Option<u8>,
+    pub height: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonHealLocations {
+    pub heal_locations: Vec<JsonHealLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JsonHealLocation {
+    pub map: String,
+    pub x: i32,
+    pub y: i32,
+}
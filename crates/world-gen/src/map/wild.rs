@@ -89,6 +89,7 @@ impl JsonWildEncounterType {
                         })
                 })
                 .collect(),
+            times: Default::default(),
         }
     }
 }
@@ -28,6 +28,8 @@ pub struct MapMappings {
     pub id: IdMappings,
     pub name: HashMap<String, String>,
     pub transition: HashMap<String, TransitionId>,
+    /// Maps the player has to ride their bike on.
+    pub force_cycling: HashSet<String>,
 }
 
 #[derive(Default, Deserialize, Serialize)]
@@ -12,6 +12,8 @@ pub struct Actions {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ActionQueue {
     Move(Direction),
+    /// Hop over a ledge, landing two tiles away
+    Jump(Direction),
     Look(Direction),
     Interact,
 }
@@ -1,6 +1,7 @@
 use crate::{
     map::movement::Elevation,
     positions::{Destination, Direction, PixelOffset, Position},
+    TILE_SIZE,
 };
 use enum_map::Enum;
 use serde::{Deserialize, Serialize};
@@ -47,6 +48,10 @@ pub struct CharacterState {
 
     #[serde(default)]
     pub actions: Actions,
+
+    /// Tiles left in the current jump
+    #[serde(default)]
+    pub jumping: u8,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum, Deserialize, Serialize)]
@@ -102,6 +107,9 @@ impl CharacterState {
     pub const NOCLIP: Capability =
         unsafe { Capability::from_bytes_unchecked([0x6E, 0x6F, 0x63, 0x6C, 0x6A, 0x70, 0, 0]) };
 
+    /// Number of tiles a jump covers
+    pub const JUMP_TILES: u8 = 2;
+
     pub fn moving(&self) -> bool {
         !self.actions.queue.is_empty() || !self.offset.is_zero()
     }
@@ -114,6 +122,19 @@ impl CharacterState {
         self.position.direction = direction;
     }
 
+    /// Height above the ground while jumping, in pixels.
+    pub fn jump_height(&self) -> f32 {
+        match self.jumping {
+            0 => 0.0,
+            left => {
+                let travelled =
+                    (Self::JUMP_TILES - left) as f32 * TILE_SIZE + self.offset.offset().abs();
+                let progress = travelled / (Self::JUMP_TILES as f32 * TILE_SIZE);
+                (progress * core::f32::consts::PI).sin() * TILE_SIZE / 2.0
+            }
+        }
+    }
+
     pub fn stop_move(&mut self) {
         self.offset.reset();
     }
@@ -147,6 +168,13 @@ impl CharacterState {
                                 self.offset =
                                     direction.pixel_offset(self.activity.speed() * 60.0 * delta);
                             }
+                            ActionQueue::Jump(direction) => {
+                                self.position.direction = direction;
+                                self.jumping = Self::JUMP_TILES;
+                                self.input_lock.increment();
+                                self.offset =
+                                    direction.pixel_offset(self.activity.speed() * 60.0 * delta);
+                            }
                             ActionQueue::Look(direction) => {
                                 self.position.direction = direction;
                             }
@@ -161,6 +189,18 @@ impl CharacterState {
                         delta * self.activity.speed() * 60.0,
                     ) {
                         self.position.coords += self.position.direction.tile_offset();
+                        if self.jumping != 0 {
+                            self.jumping -= 1;
+                            if self.jumping != 0 {
+                                // keep going until the jump lands
+                                self.offset = self
+                                    .position
+                                    .direction
+                                    .pixel_offset(delta * self.activity.speed() * 60.0);
+                                return None;
+                            }
+                            self.input_lock.decrement();
+                        }
                         self.update_sprite();
                         Some(DoMoveResult::Finished)
                     } else {
@@ -327,6 +367,7 @@ impl Default for CharacterState {
             input_lock: Default::default(),
             hidden: Default::default(),
             actions: Default::default(),
+            jumping: Default::default(),
         }
     }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use pokedex::Money;
+
 use crate::{
     character::{CharacterGroupId, CharacterState},
     map::MusicId,
@@ -18,6 +20,9 @@ pub struct NpcGroup {
 pub struct TrainerGroup {
     pub prefix: String,
     pub music: Option<MusicId>,
+    /// Prize money given for each level of the trainer's last pokemon
+    #[serde(default)]
+    pub payout: Money,
 }
 
 impl TrainerGroup {
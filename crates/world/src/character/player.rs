@@ -1,7 +1,17 @@
 use hashbrown::{HashMap, HashSet};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::{map::battle::BattleEntry, positions::Location};
+use pokedex::pokemon::Level;
+
+use crate::{
+    map::{battle::BattleEntry, data::tile::ForcedMovement},
+    positions::Location,
+    state::{
+        dex::PlayerDex,
+        storage::{OriginalTrainers, PokemonStorage},
+    },
+};
 
 use super::{
     npc::{
@@ -14,6 +24,12 @@ use super::{
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PlayerCharacter {
     pub name: String,
+    /// Trainer ID number, given out the first time the game starts
+    #[serde(default)]
+    pub id: Option<u16>,
+    /// Seconds spent playing
+    #[serde(default)]
+    pub play_time: f64,
     pub character: CharacterState,
 
     /// Player State
@@ -21,6 +37,21 @@ pub struct PlayerCharacter {
     pub battle: GlobalBattleState,
     #[serde(default)]
     pub badges: HashSet<BadgeId>,
+    /// Seconds since the unix epoch each badge was earned at
+    #[serde(default)]
+    pub badge_dates: HashMap<BadgeId, u64>,
+    /// Movement the tiles under the player are forcing
+    #[serde(default)]
+    pub forced: Option<ForcedMovement>,
+    /// Pokemon stored in the PC
+    #[serde(default)]
+    pub pc: PokemonStorage,
+    /// Species the player has seen and caught
+    #[serde(default)]
+    pub dex: PlayerDex,
+    /// Trainer ID of the original trainer of each party member, in party order
+    #[serde(default)]
+    pub original_trainers: OriginalTrainers,
 
     pub cooldown: f32,
     pub rival: String,
@@ -32,12 +63,17 @@ pub type Battled = HashSet<NpcId>;
 pub struct GlobalBattleState {
     pub battled: HashMap<Location, Battled>,
     pub battling: Option<BattleEntry>,
+    /// Steps left until Repel wears off
+    #[serde(default)]
+    pub repel: u16,
 }
 
 impl PlayerCharacter {
     pub fn new(name: impl Into<String>, rival: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            id: Default::default(),
+            play_time: Default::default(),
             character: CharacterState {
                 group: "player".parse().unwrap(),
                 capabilities: {
@@ -49,6 +85,11 @@ impl PlayerCharacter {
             },
             battle: Default::default(),
             badges: Default::default(),
+            badge_dates: Default::default(),
+            forced: Default::default(),
+            pc: Default::default(),
+            dex: Default::default(),
+            original_trainers: Default::default(),
             cooldown: Default::default(),
             rival: rival.into(),
         }
@@ -69,6 +110,54 @@ impl PlayerCharacter {
         }
     }
 
+    /// Gives the player a badge, remembering when it was earned.
+    pub fn earn_badge(&mut self, badge: BadgeId) {
+        if self.badges.insert(badge) {
+            self.badge_dates.insert(badge, Self::timestamp());
+        }
+    }
+
+    /// Whether the party member's original trainer is someone other than the player.
+    pub fn traded(&self, index: usize) -> bool {
+        self.original_trainers
+            .get(index)
+            .map(|id| Some(id) != self.id)
+            .unwrap_or_default()
+    }
+
+    /// Gives the party member an original trainer other than the player, as a trade would.
+    pub fn trade(&mut self, index: usize, random: &mut impl Rng) {
+        let id = random.gen();
+        let id = match Some(id) == self.id {
+            true => id.wrapping_add(1),
+            false => id,
+        };
+        self.original_trainers.set(index, Some(id));
+    }
+
+    /// The level each party member obeys up to.
+    /// Pokemon the player did not train themselves only obey up to the cap their badges allow.
+    pub fn obedience(&self, party: usize, cap: Level) -> Vec<Option<Level>> {
+        (0..party)
+            .map(|index| self.traded(index).then(|| cap))
+            .collect()
+    }
+
+    fn timestamp() -> u64 {
+        #[cfg(target_arch = "wasm32")]
+        {
+            0
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default()
+        }
+    }
+
     pub fn update(
         &mut self,
         message: &mut crate::state::map::MapMessage,
@@ -160,9 +249,16 @@ impl Default for PlayerCharacter {
     fn default() -> Self {
         Self {
             name: "Red".into(),
+            id: Default::default(),
+            play_time: Default::default(),
             character: Default::default(),
             battle: Default::default(),
             badges: Default::default(),
+            badge_dates: Default::default(),
+            forced: Default::default(),
+            pc: Default::default(),
+            dex: Default::default(),
+            original_trainers: Default::default(),
             cooldown: Default::default(),
             rival: "Blue".into(),
         }
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 use pokedex::{
-    item::bag::SavedBag,
+    item::{bag::SavedBag, ItemId},
     pokemon::{owned::SavedPokemon, party::Party},
+    trainer::InitTrainer,
     Money,
 };
 
@@ -47,6 +48,7 @@ pub struct TrainerEntry {
     pub sprite: CharacterGroupId,
     pub transition: TransitionId,
     pub defeat: Vec<MessagePage<MessageColor, MessageTheme>>,
+    /// Prize money for beating the trainer
     pub worth: Money,
 }
 
@@ -87,7 +89,13 @@ impl BattleEntry {
                                 theme: MessageTheme::default(),
                             })
                             .collect(),
-                        worth: trainer.character.money,
+                        worth: TrainerEntry::prize(
+                            data.trainers
+                                .get(&trainer.group)
+                                .map(|g| g.payout)
+                                .unwrap_or_default(),
+                            &trainer.character.party,
+                        ),
                     }),
                 });
             }
@@ -96,6 +104,41 @@ impl BattleEntry {
     }
 }
 
+impl TrainerEntry {
+    /// "amulet-coin"
+    pub const AMULET_COIN: ItemId = unsafe {
+        ItemId::from_bytes_unchecked([
+            0x61, 0x6d, 0x75, 0x6c, 0x65, 0x74, 0x2d, 0x63, 0x6f, 0x69, 0x6e, 0, 0, 0, 0, 0,
+        ])
+    };
+
+    /// Prize money is the payout times the level of the trainer's last pokemon.
+    pub fn prize(payout: Money, party: &[SavedPokemon]) -> Money {
+        let level = party
+            .last()
+            .map(|pokemon| pokemon.level)
+            .unwrap_or_default();
+        payout.saturating_mul(level as _)
+    }
+
+    /// The prize money won, doubled if a party member that was sent out holds an Amulet Coin.
+    pub fn winnings(&self, player: &InitTrainer, participants: &[usize]) -> Money {
+        match participants
+            .iter()
+            .flat_map(|index| player.party.get(*index))
+            .any(|pokemon| {
+                pokemon
+                    .item
+                    .as_ref()
+                    .map(|item| item.id == Self::AMULET_COIN)
+                    .unwrap_or_default()
+            }) {
+            true => self.worth.saturating_mul(2),
+            false => self.worth,
+        }
+    }
+}
+
 impl Default for BattleId {
     fn default() -> Self {
         Self::Default
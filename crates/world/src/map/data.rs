@@ -1,40 +1,67 @@
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
-use pokedex::{item::ItemId, moves::MoveId, trainer::InitTrainer};
+use pokedex::{
+    item::ItemId,
+    moves::MoveId,
+    pokemon::{Level, PokemonId},
+    trainer::InitTrainer,
+};
 
 use crate::{
     character::{
         npc::{
             group::{NpcGroup, TrainerGroup, TrainerGroupId},
-            trainer::TrainerDisable,
+            trainer::{BadgeId, TrainerDisable},
         },
+        player::PlayerCharacter,
         Capability, CharacterGroupId, CharacterState,
     },
-    positions::{Coordinate, Direction, Location, Spot},
+    positions::{Coordinate, Direction, Location, Position, Spot},
     state::map::MapState,
 };
 
 use self::tile::PaletteDataMap;
 
-use super::{chunk::Connection, warp::WarpDestination, wild::WildChances, MovementId, WorldMap};
+use super::{
+    chunk::Connection,
+    evolution::{EvolutionData, EvolutionMethod},
+    machine::MachineData,
+    medicine::MedicineData,
+    object::{ObjectGroup, ObjectType},
+    warp::WarpDestination,
+    wild::{EncounterAbility, EncounterData, EncounterModifiers, WildChances},
+    MapKind, MovementId, WorldMap,
+};
 
 pub mod tile;
 
 pub type WorldMaps = HashMap<Location, WorldMap>;
 pub type FieldMoveData = HashMap<MoveId, FieldType>;
 pub type FieldItemData = HashMap<ItemId, FieldType>;
-// pub type ObjectData = HashMap<ObjectType, Removable>;
+pub type ObjectData = HashMap<ObjectType, ObjectGroup>;
+pub type BadgeData = HashMap<BadgeId, WorldBadge>;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WorldMapData {
     pub maps: WorldMaps,
-    // pub objects: ObjectData,
+    #[serde(default)]
+    pub objects: ObjectData,
     pub palettes: PaletteDataMap,
     pub npc: WorldNpcData,
     pub wild: WildChances,
+    #[serde(default)]
+    pub encounters: EncounterData,
     pub moves: FieldMoveData,
     pub items: FieldItemData,
+    #[serde(default)]
+    pub badges: BadgeData,
+    #[serde(default)]
+    pub evolutions: EvolutionData,
+    #[serde(default)]
+    pub medicine: MedicineData,
+    #[serde(default)]
+    pub machines: MachineData,
     pub spawn: Spot,
 }
 
@@ -47,22 +74,57 @@ pub struct WorldNpcData {
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum FieldType {
     Capability(Capability),
+    /// Leaves a dungeon to the last outdoor spot
+    Escape,
+    /// Returns to the last place the player healed at
+    Teleport,
+    /// Opens the town map to fly to a visited town
+    Fly,
+    /// Opens the town map
+    TownMap,
+    /// Keeps weaker wild pokemon away for a number of steps
+    Repel(u16),
+    /// Gets on or off the bicycle
+    Bicycle,
+    /// Fishes in the water in front of the player with a rod
+    Fish(u8),
+    /// Checks for hidden items nearby
+    Itemfinder,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldBadge {
+    pub name: String,
+    /// Where the badge is shown in the badge case
+    #[serde(default)]
+    pub order: u8,
+    /// Field moves that cannot be used outside of battle without this badge
+    #[serde(default)]
+    pub moves: HashSet<MoveId>,
+    /// Traded pokemon up to this level obey the player
+    #[serde(default)]
+    pub obedience: Option<Level>,
 }
 
 impl WorldMapData {
-    pub fn update_capabilities(&self, character: &mut CharacterState, trainer: &mut InitTrainer) {
+    /// Level traded pokemon obey the player up to without any badges
+    pub const OBEDIENCE: Level = 10;
+
+    pub fn update_capabilities(&self, player: &mut PlayerCharacter, trainer: &mut InitTrainer) {
         fn set(can: bool, character: &mut CharacterState, t: &FieldType) {
+            let capability = match t {
+                FieldType::Capability(capability) => capability,
+                FieldType::Bicycle => &CharacterState::CYCLE,
+                _ => return,
+            };
             match can {
-                true => match t {
-                    FieldType::Capability(capability) => {
-                        character.capabilities.insert(*capability);
-                    }
-                },
-                false => match t {
-                    FieldType::Capability(capability) => {
-                        character.capabilities.remove(capability);
-                    }
-                },
+                true => {
+                    character.capabilities.insert(*capability);
+                }
+                false => {
+                    character.capabilities.remove(capability);
+                }
             }
         }
 
@@ -71,8 +133,9 @@ impl WorldMapData {
                 trainer
                     .party
                     .iter()
-                    .any(|p| p.moves.iter().any(|m| &m.0.id == id)),
-                character,
+                    .any(|p| p.moves.iter().any(|m| &m.0.id == id))
+                    && self.has_badges_for(player, id),
+                &mut player.character,
                 t,
             );
         }
@@ -80,12 +143,94 @@ impl WorldMapData {
         for (id, t) in self.items.iter() {
             set(
                 trainer.bag.iter().any(|stack| &stack.item.id == id),
-                character,
+                &mut player.character,
                 t,
             );
         }
     }
 
+    /// Names of the maps each species can be found on in the wild.
+    pub fn areas(&self) -> HashMap<PokemonId, Vec<String>> {
+        let mut areas = HashMap::<PokemonId, Vec<String>>::new();
+        for map in self.maps.values() {
+            for species in map
+                .wild
+                .iter()
+                .flat_map(|wild| wild.values())
+                .flat_map(|entry| entry.species())
+            {
+                let names = areas.entry(species).or_default();
+                if !names.contains(&map.name) {
+                    names.push(map.name.clone());
+                }
+            }
+        }
+        areas.values_mut().for_each(|names| names.sort());
+        areas
+    }
+
+    /// Checks if an item is used on a party member, like medicine, TMs or evolution stones.
+    pub fn targets_pokemon(&self, id: &ItemId) -> bool {
+        self.medicine.contains_key(id)
+            || self.machines.contains_key(id)
+            || self
+                .evolutions
+                .values()
+                .flatten()
+                .any(|evolution| evolution.method == EvolutionMethod::Item(*id))
+    }
+
+    /// Checks if the player has every badge needed to use a move in the field.
+    pub fn has_badges_for(&self, player: &PlayerCharacter, id: &MoveId) -> bool {
+        self.badges
+            .iter()
+            .filter(|(.., badge)| badge.moves.contains(id))
+            .all(|(badge, ..)| player.badges.contains(badge))
+    }
+
+    /// Highest level traded pokemon obey the player at.
+    pub fn obedience(&self, player: &PlayerCharacter) -> Level {
+        player
+            .badges
+            .iter()
+            .flat_map(|badge| self.badges.get(badge))
+            .flat_map(|badge| badge.obedience)
+            .fold(Self::OBEDIENCE, Level::max)
+    }
+
+    pub fn encounter_modifiers(
+        &self,
+        state: &MapState,
+        trainer: &InitTrainer,
+    ) -> EncounterModifiers {
+        let mut modifiers = EncounterModifiers {
+            rate: self.encounters.multiplier,
+            time: state.clock.time_of_day(),
+            ..Default::default()
+        };
+        if let Some(lead) = trainer.party.iter().find(|pokemon| !pokemon.fainted()) {
+            if state.player.battle.repel != 0 {
+                modifiers.min_level = Some(lead.level);
+            }
+            match self.encounters.abilities.get(&lead.pokemon.id) {
+                Some(EncounterAbility::Stench) => modifiers.rate *= 0.5,
+                Some(EncounterAbility::Illuminate) => modifiers.rate *= 2.0,
+                Some(EncounterAbility::Intimidate | EncounterAbility::KeenEye) => {
+                    modifiers.weak_level = lead.level.checked_sub(5);
+                }
+                None => (),
+            }
+            if let Some(rate) = lead
+                .item
+                .as_ref()
+                .and_then(|item| self.encounters.items.get(&item.id))
+            {
+                modifiers.rate *= rate;
+            }
+        }
+        modifiers
+    }
+
     pub fn connection_movement(
         &self,
         direction: Direction,
@@ -107,37 +252,48 @@ impl WorldMapData {
         })
     }
 
-    pub fn post_battle(&self, state: &mut MapState, trainer: &mut InitTrainer, winner: bool) {
+    pub fn post_battle(
+        &self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        winner: bool,
+        participants: &[usize],
+    ) {
         state.player.character.locked.decrement();
         let entries = std::mem::take(&mut state.player.battle.battling);
         if winner {
             for entry in entries {
-                if let Some(trainer) = entry.trainer {
+                if let Some(opponent) = entry.trainer {
+                    let winnings = opponent.winnings(trainer, participants);
+                    trainer.money = trainer.money.saturating_add(winnings);
+                    if let Some(badge) = opponent.badge {
+                        state.player.earn_badge(badge);
+                    }
                     state.player.character.end_interact();
                     if let Some(character) = state
                         .entities
                         .get_mut(&state.location)
-                        .and_then(|state| state.npcs.get_mut(&trainer.id))
+                        .and_then(|state| state.npcs.get_mut(&opponent.id))
                     {
                         character.end_interact();
                     }
                     if let Some(npc) = self
                         .maps
-                        .get(&trainer.location)
-                        .and_then(|map| map.npcs.get(&trainer.id))
+                        .get(&opponent.location)
+                        .and_then(|map| map.npcs.get(&opponent.id))
                         .and_then(|npc| npc.trainer.as_ref())
                     {
                         match &npc.disable {
                             TrainerDisable::DisableSelf => {
-                                state.player.battle.insert(&trainer.location, trainer.id);
+                                state.player.battle.insert(&opponent.location, opponent.id);
                             }
                             TrainerDisable::Many(others) => {
-                                state.player.battle.insert(&trainer.location, trainer.id);
+                                state.player.battle.insert(&opponent.location, opponent.id);
                                 state
                                     .player
                                     .battle
                                     .battled
-                                    .get_mut(&trainer.location)
+                                    .get_mut(&opponent.location)
                                     .unwrap()
                                     .extend(others);
                             }
@@ -146,21 +302,45 @@ impl WorldMapData {
                 }
             }
         } else {
-            self.whiteout(state, trainer);
+            self.whiteout(trainer);
         }
-        self.update_capabilities(&mut state.player.character, trainer);
+        self.update_capabilities(&mut state.player, trainer);
     }
 
-    pub fn whiteout(&self, state: &mut MapState, trainer: &mut InitTrainer) {
-        let Spot { location, position } = state.places.heal.unwrap_or(self.spawn);
-        state.location = location;
-        state.player.character.position = position;
+    /// Heals the party after the player whites out, losing half their money.
+    pub fn whiteout(&self, trainer: &mut InitTrainer) {
+        trainer.money /= 2;
         trainer.party.iter_mut().for_each(|o| o.heal(None, None));
     }
 
+    /// Where the player returns to after whiting out, and whether it is their home.
+    pub fn heal_spot(&self, state: &MapState) -> (Spot, bool) {
+        let spot = state.places.heal.unwrap_or(self.spawn);
+        (spot, spot.location == self.spawn.location)
+    }
+
     pub fn warp(&self, state: &mut MapState, destination: WarpDestination) -> bool {
-        match self.maps.contains_key(&destination.location) {
-            true => {
+        match self.maps.get(&destination.location) {
+            Some(map) => {
+                if map.settings.kind == MapKind::Dungeon
+                    && self
+                        .maps
+                        .get(&state.location)
+                        .map(|map| map.settings.kind != MapKind::Dungeon)
+                        .unwrap_or_default()
+                {
+                    // the tile the player stepped onto the entrance from
+                    let position = &state.player.character.position;
+                    let direction = position.direction.inverse();
+                    state.places.cave = Some(Spot {
+                        location: state.location,
+                        position: Position {
+                            coords: position.coords.in_direction(direction),
+                            direction,
+                            ..Default::default()
+                        },
+                    });
+                }
                 MapState::warp(
                     &mut state.location,
                     &mut state.player.character,
@@ -168,7 +348,7 @@ impl WorldMapData {
                 );
                 true
             }
-            false => false,
+            None => false,
         }
     }
 }
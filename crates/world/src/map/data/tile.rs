@@ -31,6 +31,21 @@ pub struct PaletteData {
     pub cliffs: HashMap<Direction, Vec<TileId>>,
     #[serde(default)]
     pub forwarding: Vec<TileId>,
+    /// Tiles that pull cyclists downhill
+    #[serde(default)]
+    pub slopes: Vec<TileId>,
+    /// Tiles the player slides across
+    #[serde(default)]
+    pub ice: Vec<TileId>,
+    /// Tiles that spin the player in a direction until they reach a stop tile
+    #[serde(default)]
+    pub spinners: HashMap<Direction, Vec<TileId>>,
+    /// Water that carries the player in a direction
+    #[serde(default)]
+    pub currents: HashMap<Direction, Vec<TileId>>,
+    /// Tiles that stop the player from spinning
+    #[serde(default)]
+    pub stops: Vec<TileId>,
     #[serde(default)]
     pub warp: HashMap<TileId, WarpTile>,
 }
@@ -42,6 +57,15 @@ pub enum MapWildType {
     All,
 }
 
+/// Movement the player cannot control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForcedMovement {
+    /// Moves while on ice or currents
+    Slide(Direction),
+    /// Moves until a stop tile or an obstacle
+    Spin(Direction),
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum WarpTile {
     Door,
@@ -55,6 +79,28 @@ pub enum WarpTile {
 //     Water,
 // }
 
+impl PaletteData {
+    /// Gets the forced movement a tile starts, if any.
+    /// `direction` is the direction the player entered the tile in.
+    pub fn forced(&self, tile: &TileId, direction: Direction) -> Option<ForcedMovement> {
+        fn find(tiles: &HashMap<Direction, Vec<TileId>>, tile: &TileId) -> Option<Direction> {
+            tiles
+                .iter()
+                .find(|(.., tiles)| tiles.contains(tile))
+                .map(|(direction, ..)| *direction)
+        }
+
+        find(&self.spinners, tile)
+            .map(ForcedMovement::Spin)
+            .or_else(|| find(&self.currents, tile).map(ForcedMovement::Slide))
+            .or_else(|| {
+                self.ice
+                    .contains(tile)
+                    .then(|| ForcedMovement::Slide(direction))
+            })
+    }
+}
+
 impl MapWildType {
     pub fn contains(&self, tile: &TileId) -> bool {
         match self {
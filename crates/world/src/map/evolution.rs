@@ -0,0 +1,58 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use pokedex::{
+    item::ItemId,
+    pokemon::{Level, PokemonId},
+};
+
+/// What each species evolves into, and how.
+pub type EvolutionData = HashMap<PokemonId, Vec<Evolution>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evolution {
+    pub into: PokemonId,
+    pub method: EvolutionMethod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvolutionMethod {
+    /// Evolves after leveling up to at least this level
+    Level(Level),
+    /// Evolves when the item is used on it (i.e. evolution stones)
+    Item(ItemId),
+    /// Evolves when traded
+    Trade,
+}
+
+/// What caused an evolution check.
+#[derive(Debug, Clone, Copy)]
+pub enum EvolutionTrigger {
+    /// The pokemon leveled up to this level
+    Level(Level),
+    Item(ItemId),
+    Trade,
+}
+
+impl EvolutionMethod {
+    pub fn matches(&self, trigger: &EvolutionTrigger) -> bool {
+        match (self, trigger) {
+            (Self::Level(required), EvolutionTrigger::Level(level)) => level >= required,
+            (Self::Item(required), EvolutionTrigger::Item(item)) => item == required,
+            (Self::Trade, EvolutionTrigger::Trade) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Finds what a species evolves into when triggered.
+pub fn evolution(
+    data: &EvolutionData,
+    species: &PokemonId,
+    trigger: EvolutionTrigger,
+) -> Option<PokemonId> {
+    data.get(species)?
+        .iter()
+        .find(|evolution| evolution.method.matches(&trigger))
+        .map(|evolution| evolution.into)
+}
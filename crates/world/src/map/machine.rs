@@ -0,0 +1,25 @@
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use pokedex::{item::ItemId, moves::MoveId, pokemon::PokemonId};
+
+/// TMs and HMs that teach a move to a party member.
+pub type MachineData = HashMap<ItemId, Machine>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Machine {
+    /// The move the machine teaches
+    pub teaches: MoveId,
+    /// Pokemon that are able to learn the move
+    #[serde(default)]
+    pub pokemon: HashSet<PokemonId>,
+    /// HMs are kept after they are used
+    #[serde(default)]
+    pub reusable: bool,
+}
+
+impl Machine {
+    pub fn compatible(&self, pokemon: &PokemonId) -> bool {
+        self.pokemon.contains(pokemon)
+    }
+}
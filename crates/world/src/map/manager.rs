@@ -3,30 +3,40 @@ use std::sync::Arc;
 use rand::{prelude::IteratorRandom, Rng};
 
 use pokedex::{
-    item::Item,
-    moves::Move,
-    pokemon::Pokemon,
-    trainer::{InitTrainer, Trainer},
+    item::{Item, ItemCategory, ItemId, ItemStack},
+    moves::{Move, MoveId},
+    pokemon::{owned::SavedPokemon, Pokemon},
+    trainer::InitTrainer,
     Dex,
 };
 
+use text::{MessagePage, MessageState, MessageStates};
+
 use crate::{
     character::{
-        action::ActionQueue, player::PlayerCharacter, Activity, CharacterState, DoMoveResult,
+        action::ActionQueue, player::PlayerCharacter, Activity, Capability, CharacterState,
+        DoMoveResult,
+    },
+    map::{
+        object::{DestroyMethod, MapObject, ObjectId},
+        MapKind, MovementId, MusicId, WarpDestination, WorldMap,
     },
-    map::{MovementId, WarpDestination, WorldMap},
-    positions::{BoundingBox, Direction, Location},
+    message::{MessageColor, MessageTheme},
+    positions::{BoundingBox, Coordinate3d, Destination, Direction, Location, Spot},
     random::WorldRandoms,
     script::WorldScriptingEngine,
     state::{
-        map::{MapEvent, MapState},
+        map::{MapEvent, MapMessage, MapPrompt, MapState, PromptKind, Whiteout, CYCLING_MUSIC},
+        storage::PARTY_SIZE,
         WorldState,
     },
 };
 
 use super::{
-    data::WorldMapData,
+    data::{tile::ForcedMovement, FieldType, WorldMapData},
+    machine::Machine,
     movement::{Elevation, MapMovementResult},
+    wild::WildType,
 };
 
 pub struct WorldMapManager<S: WorldScriptingEngine> {
@@ -43,9 +53,16 @@ pub struct WorldMapManager<S: WorldScriptingEngine> {
 pub enum InputEvent {
     Move(Direction),
     Interact,
+    /// Gets on or off the bicycle
+    Bicycle,
 }
 
 impl<S: WorldScriptingEngine> WorldMapManager<S> {
+    /// No Surf theme is bundled with the game's music, so surfing plays the cycling theme
+    pub const SURF_MUSIC: MusicId = CYCLING_MUSIC;
+
+    pub const NO_EFFECT: &'static str = "It won't have any effect.";
+
     pub fn contains(&self, location: &Location) -> bool {
         self.data.maps.contains_key(location)
     }
@@ -54,12 +71,13 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         self.data.maps.get(location)
     }
 
-    pub fn on_warp<R: Rng, P, B>(
+    pub fn on_warp<R: Rng>(
         &self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
     ) {
+        state.map.player.forced = None;
         self.on_map_change(&mut state.map);
         self.on_tile(state, randoms, trainer);
     }
@@ -71,23 +89,59 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
     }
 
     pub fn on_change(&self, map: &WorldMap, state: &mut MapState) {
-        state.events.push(MapEvent::PlayMusic(Some(map.music)));
+        let character = &mut state.player.character;
+        match character.activity {
+            Activity::Cycling if !map.settings.cycling => character.activity = Activity::Walking,
+            Activity::Walking | Activity::Running
+                if map.settings.force_cycling
+                    && character.capabilities.contains(&CharacterState::CYCLE) =>
+            {
+                character.activity = Activity::Cycling
+            }
+            _ => (),
+        }
+        let music = match character.activity {
+            Activity::Cycling => CYCLING_MUSIC,
+            _ => map.music,
+        };
+        state.events.push(MapEvent::PlayMusic(Some(music)));
         state.update_objects(&self.data);
-        // check for cave here and add last spot non cave for escape rope
+        if map.settings.fly_position.is_some() {
+            state.places.visited.insert(map.id);
+        }
     }
 
-    pub fn update_capabilities(&self, character: &mut CharacterState, trainer: &mut InitTrainer) {
-        self.data.update_capabilities(character, trainer);
+    pub fn update_capabilities(&self, player: &mut PlayerCharacter, trainer: &mut InitTrainer) {
+        self.data.update_capabilities(player, trainer);
     }
 
     pub fn input(&self, state: &mut MapState, input: InputEvent) {
         match input {
-            InputEvent::Move(direction) => self.try_move_player(state, direction),
+            InputEvent::Move(direction) => {
+                if state.player.forced.is_none() {
+                    self.try_move_player(state, direction)
+                }
+            }
             InputEvent::Interact => state.player.character.queue_interact(true),
+            InputEvent::Bicycle => {
+                if state
+                    .player
+                    .character
+                    .capabilities
+                    .contains(&CharacterState::CYCLE)
+                {
+                    self.use_field(state, &FieldType::Bicycle);
+                }
+            }
         }
     }
 
-    pub fn try_interact(&self, state: &mut MapState) {
+    pub fn try_interact<R: Rng>(
+        &self,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        randoms: &mut WorldRandoms<R>,
+    ) {
         if let Some(map) = self.data.maps.get(&state.location) {
             let pos = if map
                 .tile(state.player.character.position.coords)
@@ -115,15 +169,23 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
             {
                 if npc.interact_from(&pos) {
                     state.player.character.input_lock.increment();
-                    break;
+                    return;
                 }
             }
 
             let forward = state.player.character.position.forwards();
 
-            // if let Some(object) = map.object_at(&forward) {
-
-            // }
+            if let Some((id, object)) = map.object_at(&forward) {
+                self.try_break(map, *id, object, state, trainer, randoms);
+            } else if map.pcs.contains(&forward)
+                && state.player.character.position.direction == Direction::Up
+            {
+                state.events.push(MapEvent::OpenPc);
+            } else if map.local_movement(forward, state) == Some(Elevation::WATER)
+                && state.player.character.activity != Activity::Swimming
+            {
+                self.try_surf(state);
+            }
 
             // if let Some(item) = map.item_at(&forward) {
             //     item.pickup(&map.id, forward, state);
@@ -131,6 +193,607 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         }
     }
 
+    fn try_break<R: Rng>(
+        &self,
+        map: &WorldMap,
+        id: ObjectId,
+        object: &MapObject,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        randoms: &mut WorldRandoms<R>,
+    ) {
+        let group = match object.group {
+            Some(group) => group,
+            None => return,
+        };
+
+        if state
+            .entities
+            .get(&map.id)
+            .map(|entities| entities.is_removed(&id))
+            .unwrap_or_default()
+        {
+            return;
+        }
+
+        if let Some(destroy) = self
+            .data
+            .objects
+            .get(&group)
+            .and_then(|group| group.destroy.as_ref())
+        {
+            let character = &state.player.character;
+            if character.capabilities.contains(&CharacterState::NOCLIP)
+                || match &destroy.method {
+                    DestroyMethod::Move(id) => trainer
+                        .party
+                        .iter()
+                        .any(|p| p.moves.iter().any(|m| &m.0.id == id)),
+                    DestroyMethod::Capability(capability) => {
+                        character.capabilities.contains(capability)
+                    }
+                }
+            {
+                let entities = state.entities.entry(map.id).or_default();
+                match destroy.regeneratable {
+                    true => entities.regenerating.insert(id),
+                    false => entities.removed.insert(id),
+                };
+                state
+                    .events
+                    .push(MapEvent::BreakObject(object.coordinate, group));
+
+                if let Some(t) = &destroy.wild {
+                    if state
+                        .player
+                        .character
+                        .capabilities
+                        .contains(&CharacterState::ENCOUNTERS)
+                    {
+                        let modifiers = self.data.encounter_modifiers(state, trainer);
+                        map.wild_battle(&self.data, state, randoms, t, &modifiers);
+                    }
+                }
+            }
+        }
+    }
+
+    fn try_surf(&self, state: &mut MapState) {
+        let mut pages = vec![MessagePage {
+            lines: vec!["The water is dyed a deep blue...".to_owned()],
+            wait: None,
+            color: None,
+            theme: Default::default(),
+        }];
+
+        if state
+            .player
+            .character
+            .capabilities
+            .contains(&CharacterState::SWIM)
+        {
+            pages.push(MessagePage {
+                lines: vec!["Would you like to SURF?".to_owned()],
+                // stays on screen until the prompt is answered
+                wait: Some(f32::MAX),
+                color: None,
+                theme: Default::default(),
+            });
+            state.prompt = Some(MapPrompt::new(PromptKind::Surf));
+        }
+
+        state.player.character.input_lock.increment();
+        state.message = MessageStates::Running(MessageState {
+            pages,
+            ..Default::default()
+        });
+    }
+
+    fn on_prompt(
+        &self,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        kind: PromptKind,
+        answer: bool,
+    ) {
+        match kind {
+            PromptKind::Surf => match answer {
+                true => {
+                    state.message = match self.field_user(&CharacterState::SWIM, trainer) {
+                        Some((pokemon, name)) => MessageStates::Running(MessageState {
+                            pages: vec![MessagePage {
+                                lines: vec![format!("{} used {}!", pokemon, name)],
+                                wait: None,
+                                color: None,
+                                theme: Default::default(),
+                            }],
+                            ..Default::default()
+                        }),
+                        None => Self::finished_message(),
+                    };
+                    let character = &mut state.player.character;
+                    character.activity = Activity::Swimming;
+                    Elevation::change(&mut character.position.elevation, Elevation::WATER);
+                    state.events.push(MapEvent::PlayerHop);
+                    state
+                        .events
+                        .push(MapEvent::PlayMusic(Some(Self::SURF_MUSIC)));
+                }
+                false => state.message = Self::finished_message(),
+            },
+        }
+    }
+
+    /// Credits prize money after a win, or starts the whiteout sequence after a loss.
+    /// Participants are the party members that were sent out during the battle.
+    pub fn post_battle(
+        &self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        winner: bool,
+        participants: &[usize],
+    ) {
+        let money = trainer.money;
+        self.data.post_battle(state, trainer, winner, participants);
+        if winner && trainer.money > money {
+            let message = format!(
+                "{} got ${} for winning!",
+                state.player.name,
+                trainer.money - money
+            );
+            state.player.character.input_lock.increment();
+            state.message = Self::message(&message);
+        } else if !winner {
+            let name = &state.player.name;
+            let place = match self.data.heal_spot(state).1 {
+                true => "back home",
+                false => "to a POKéMON CENTER",
+            };
+            let pages = vec![
+                vec![format!("{} is out of usable POKéMON!", name)],
+                vec![format!(
+                    "{} panicked and lost ${}...",
+                    name,
+                    money - trainer.money
+                )],
+                vec!["... ... ... ...".to_owned()],
+                vec![
+                    format!("{} scurried {},", name, place),
+                    "protecting the exhausted and fainted".to_owned(),
+                ],
+                vec!["POKéMON from further harm...".to_owned()],
+            ];
+            state.player.character.input_lock.increment();
+            state.message = Self::pages(pages);
+            state.whiteout = Some(Whiteout::Warping);
+        }
+    }
+
+    /// Moves the whiteout sequence along once its message or warp has finished.
+    fn whiteout(&self, state: &mut MapState) {
+        if !matches!(state.message, MessageStates::None) || state.warp.is_some() {
+            return;
+        }
+        match state.whiteout {
+            Some(Whiteout::Warping) => {
+                let (Spot { location, position }, home) = self.data.heal_spot(state);
+                let mut position = Destination::from(position);
+                if !home {
+                    // the heal spot is in front of the nurse's counter
+                    position.direction = Some(Direction::Up);
+                }
+                state.player.character.activity = Activity::Walking;
+                state.warp = Some(WarpDestination { location, position });
+                state.whiteout = Some(Whiteout::Healing);
+            }
+            // the warp transition holds the input lock until it has faded back in
+            Some(Whiteout::Healing) if !state.player.character.input_lock.active() => {
+                let name = &state.player.name;
+                let pages = match self.data.heal_spot(state).1 {
+                    true => vec![
+                        vec![
+                            format!("MOM: {}!", name),
+                            "You should take a quick rest.".to_owned(),
+                        ],
+                        vec![
+                            "Oh, good! You and your POKéMON".to_owned(),
+                            "are looking great.".to_owned(),
+                        ],
+                        vec!["Take care now!".to_owned()],
+                    ],
+                    false => vec![
+                        vec![
+                            "First, you should restore your".to_owned(),
+                            "POKéMON to full health.".to_owned(),
+                        ],
+                        vec![
+                            "Your POKéMON have been healed".to_owned(),
+                            "to perfect health.".to_owned(),
+                        ],
+                        vec!["We hope you excel!".to_owned()],
+                    ],
+                };
+                state.player.character.input_lock.increment();
+                state.message = Self::pages(pages);
+                state.whiteout = None;
+            }
+            _ => (),
+        }
+    }
+
+    /// Uses an item from the bag in the field. Returns true if the item should be taken from the bag.
+    pub fn use_item(&self, state: &mut MapState, id: &ItemId) -> bool {
+        match self.data.items.get(id) {
+            Some(t) => {
+                self.use_field(state, t) && matches!(t, FieldType::Escape | FieldType::Repel(..))
+            }
+            None => {
+                state.player.character.input_lock.increment();
+                state.message = Self::message(Self::NO_EFFECT);
+                false
+            }
+        }
+    }
+
+    /// Uses medicine or a TM from the bag on a party member. Returns true if the item should be taken from the bag.
+    pub fn use_item_on(
+        &self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        pokemon: usize,
+        id: &ItemId,
+    ) -> bool {
+        if let Some(machine) = self.data.machines.get(id) {
+            return self.use_machine(state, trainer, pokemon, machine);
+        }
+        let message = trainer
+            .party
+            .get_mut(pokemon)
+            .zip(self.data.medicine.get(id))
+            .and_then(|(pokemon, medicine)| medicine.apply(pokemon));
+        state.player.character.input_lock.increment();
+        state.message = Self::message(message.as_deref().unwrap_or(Self::NO_EFFECT));
+        message.is_some()
+    }
+
+    /// Teaches a party member the move in a TM or HM if it is able to learn it.
+    fn use_machine(
+        &self,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        index: usize,
+        machine: &Machine,
+    ) -> bool {
+        let (pokemon, m) = match (
+            trainer.party.get(index),
+            self.movedex.try_get(&machine.teaches),
+        ) {
+            (Some(pokemon), Some(m)) => (pokemon, m),
+            _ => return false,
+        };
+        let message = if pokemon.moves.iter().any(|known| known.0.id == m.id) {
+            format!("{} already knows {}.", pokemon.name(), m.name)
+        } else if !machine.compatible(&pokemon.pokemon.id) {
+            format!("{} can't learn {}.", pokemon.name(), m.name)
+        } else {
+            state.events.push(MapEvent::LearnMove(index, m.id));
+            return !machine.reusable;
+        };
+        state.player.character.input_lock.increment();
+        state.message = Self::message(&message);
+        false
+    }
+
+    /// Gives an item from the bag to a party member to hold.
+    /// Any item it was already holding is put back in the bag.
+    pub fn give_held_item(
+        &self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        pokemon: usize,
+        id: &ItemId,
+    ) {
+        let (stack, pokemon) = match (
+            trainer.bag.get_mut(id).filter(|stack| stack.count != 0),
+            trainer.party.get_mut(pokemon),
+        ) {
+            (Some(stack), Some(pokemon)) => (stack, pokemon),
+            _ => return,
+        };
+        let item = stack.item.clone();
+        let message = if matches!(item.category, ItemCategory::KeyItems) {
+            format!("The {} can't be held.", item.name)
+        } else {
+            stack.count -= 1;
+            match pokemon.item.replace(item.clone()) {
+                Some(held) => {
+                    let message = format!(
+                        "The {} was taken and replaced with the {}.",
+                        held.name, item.name
+                    );
+                    trainer.bag.insert(ItemStack {
+                        item: held,
+                        count: 1,
+                    });
+                    message
+                }
+                None => format!("{} was given the {} to hold.", pokemon.name(), item.name),
+            }
+        };
+        state.player.character.input_lock.increment();
+        state.message = Self::message(&message);
+    }
+
+    /// Puts the item a party member is holding in the bag.
+    pub fn take_held_item(&self, state: &mut MapState, trainer: &mut InitTrainer, pokemon: usize) {
+        let message = match trainer.party.get_mut(pokemon) {
+            Some(pokemon) => match pokemon.item.take() {
+                Some(item) => {
+                    let message = format!("Received the {} from {}.", item.name, pokemon.name());
+                    trainer.bag.insert(ItemStack { item, count: 1 });
+                    message
+                }
+                None => format!("{} isn't holding anything.", pokemon.name()),
+            },
+            None => return,
+        };
+        state.player.character.input_lock.increment();
+        state.message = Self::message(&message);
+    }
+
+    /// Fishes in the water in front of the player, starting a battle if something bites.
+    pub fn fish<R: Rng>(
+        &self,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        randoms: &mut WorldRandoms<R>,
+        rod: u8,
+    ) {
+        if let Some(map) = self.data.maps.get(&state.location) {
+            let modifiers = self.data.encounter_modifiers(state, trainer);
+            map.wild_battle(
+                &self.data,
+                state,
+                randoms,
+                &WildType::Fishing(rod),
+                &modifiers,
+            );
+            if state.player.battle.battling.is_none() {
+                state.player.character.input_lock.increment();
+                state.message = Self::message("Not even a nibble...");
+            }
+        }
+    }
+
+    /// Uses a move known by a party member in the field. Returns true if the move was used.
+    pub fn use_move(
+        &self,
+        state: &mut MapState,
+        trainer: &InitTrainer,
+        pokemon: usize,
+        id: &MoveId,
+    ) -> bool {
+        match trainer
+            .party
+            .get(pokemon)
+            .filter(|pokemon| pokemon.moves.iter().any(|m| &m.0.id == id))
+            .and(self.data.moves.get(id))
+        {
+            Some(t) => self.data.has_badges_for(&state.player, id) && self.use_field(state, t),
+            None => false,
+        }
+    }
+
+    fn use_field(&self, state: &mut MapState, t: &FieldType) -> bool {
+        let map = match self.data.maps.get(&state.location) {
+            Some(map) => map,
+            None => return false,
+        };
+        let kind = map.settings.kind;
+        let spot = match t {
+            FieldType::Escape => match kind {
+                MapKind::Dungeon => state.places.cave,
+                _ => None,
+            },
+            FieldType::Teleport => match kind {
+                MapKind::Outdoor => Some(state.places.heal.unwrap_or(self.data.spawn)),
+                _ => None,
+            },
+            FieldType::Fly | FieldType::TownMap => {
+                let fly = matches!(t, FieldType::Fly);
+                if !fly || kind == MapKind::Outdoor {
+                    state.events.push(MapEvent::OpenTownMap(fly));
+                    return true;
+                }
+                None
+            }
+            FieldType::Repel(steps) => {
+                let repel = &mut state.player.battle.repel;
+                if *repel == 0 {
+                    *repel = *steps;
+                    return true;
+                }
+                state.player.character.input_lock.increment();
+                state.message = Self::message("But the effects of a REPEL lingered from earlier.");
+                return false;
+            }
+            FieldType::Bicycle => {
+                let character = &mut state.player.character;
+                match character.activity {
+                    Activity::Cycling if !map.settings.force_cycling => {
+                        character.activity = Activity::Walking;
+                        state.events.push(MapEvent::PlayMusic(Some(map.music)));
+                        return true;
+                    }
+                    Activity::Walking | Activity::Running if map.settings.cycling => {
+                        character.activity = Activity::Cycling;
+                        state.events.push(MapEvent::PlayMusic(Some(CYCLING_MUSIC)));
+                        return true;
+                    }
+                    _ => None,
+                }
+            }
+            FieldType::Fish(rod) => {
+                let forward = state.player.character.position.forwards();
+                if map.local_movement(forward, state) == Some(Elevation::WATER) {
+                    state.events.push(MapEvent::Fish(*rod));
+                    return true;
+                }
+                None
+            }
+            FieldType::Itemfinder => {
+                let coords = state.player.character.position.coords;
+                let entities = state.entities.get(&map.id);
+                // hidden objects within the screen around the player
+                let found = map.objects.iter().any(|(id, object)| {
+                    object.group.is_none()
+                        && !entities
+                            .map(|entities| entities.is_removed(id))
+                            .unwrap_or_default()
+                        && (object.coordinate.x - coords.x).abs() <= 7
+                        && (object.coordinate.y - coords.y).abs() <= 5
+                });
+                state.player.character.input_lock.increment();
+                state.message = Self::message(match found {
+                    true => "Huh? The ITEMFINDER's responding!",
+                    false => "... Nope! There's no response.",
+                });
+                return true;
+            }
+            FieldType::Capability(..) => None,
+        };
+        match spot {
+            Some(Spot { location, position }) => {
+                state.player.character.activity = Activity::Walking;
+                state.warp = Some(WarpDestination {
+                    location,
+                    position: position.into(),
+                });
+                true
+            }
+            None => {
+                state.player.character.input_lock.increment();
+                state.message = Self::message("Can't use that here.");
+                false
+            }
+        }
+    }
+
+    /// Flies to a visited town. Returns false if the town cannot be flown to.
+    pub fn fly(&self, state: &mut MapState, location: &Location) -> bool {
+        match state
+            .places
+            .visited
+            .contains(location)
+            .then(|| self.data.maps.get(location))
+            .flatten()
+            .and_then(|map| map.settings.fly_position)
+        {
+            Some(coords) => {
+                state.player.character.activity = Activity::Walking;
+                state.warp = Some(WarpDestination {
+                    location: *location,
+                    position: Destination {
+                        coords,
+                        direction: Some(Direction::Down),
+                    },
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a pokemon to the party, or deposits it in the PC if the party is full.
+    pub fn give_pokemon<R: Rng>(
+        &self,
+        state: &mut MapState,
+        trainer: &mut InitTrainer,
+        random: &mut R,
+        pokemon: SavedPokemon,
+    ) {
+        state.player.dex.catch(pokemon.pokemon);
+        if trainer.party.len() < PARTY_SIZE {
+            match pokemon.init(random, &self.pokedex, &self.movedex, &self.itemdex) {
+                Some(pokemon) => {
+                    let index = trainer.party.len();
+                    trainer.party.push(pokemon);
+                    state.player.original_trainers.set(index, state.player.id);
+                }
+                None => log::warn!("Could not initialize pokemon given to player"),
+            }
+            return;
+        }
+        let name = self
+            .pokedex
+            .try_get(&pokemon.pokemon)
+            .map(|pokemon| pokemon.name.clone())
+            .unwrap_or_default();
+        let message = match state.player.pc.deposit(pokemon, state.player.id) {
+            Ok(index) => format!(
+                "{} was transferred to the PC. It was placed in {}.",
+                name, state.player.pc.boxes[index].name
+            ),
+            Err(..) => format!("There's no more room for {}!", name),
+        };
+        state.player.character.input_lock.increment();
+        state.message = Self::message(&message);
+    }
+
+    /// Finds the name of a pokemon in the party that gives a capability and the move it uses.
+    fn field_user(
+        &self,
+        capability: &Capability,
+        trainer: &InitTrainer,
+    ) -> Option<(String, String)> {
+        self.data
+            .moves
+            .iter()
+            .filter(|(.., t)| matches!(t, FieldType::Capability(c) if c == capability))
+            .find_map(|(id, ..)| {
+                trainer.party.iter().find_map(|pokemon| {
+                    pokemon
+                        .moves
+                        .iter()
+                        .find(|m| &m.0.id == id)
+                        .map(|m| (pokemon.name().to_owned(), m.0.name.to_uppercase()))
+                })
+            })
+    }
+
+    fn message(line: &str) -> MapMessage {
+        MessageStates::Running(MessageState {
+            pages: vec![MessagePage {
+                lines: vec![line.to_owned()],
+                wait: None,
+                color: None,
+                theme: Default::default(),
+            }],
+            ..Default::default()
+        })
+    }
+
+    fn pages(pages: Vec<Vec<String>>) -> MapMessage {
+        MessageStates::Running(MessageState {
+            pages: pages
+                .into_iter()
+                .map(|lines| MessagePage {
+                    lines,
+                    wait: None,
+                    color: None,
+                    theme: Default::default(),
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    fn finished_message() -> MapMessage {
+        MessageStates::Finished(
+            MessageState::<MessageColor, MessageTheme>::DEFAULT_COOLDOWN.unwrap_or_default(),
+        )
+    }
+
     pub fn move_npcs<R: Rng>(
         &self,
         state: &mut MapState,
@@ -224,11 +887,11 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         }
     }
 
-    pub fn on_tile<R: Rng, P, B>(
+    pub fn on_tile<R: Rng>(
         &self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
     ) {
         // state.events.push(MapEvent::OnTile);
         self.scripting
@@ -243,7 +906,7 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
                 .contains(&CharacterState::ENCOUNTERS)
         {
             if let Some(map) = self.data.maps.get(&state.map.location) {
-                map.try_wild_battle(&self.data, &mut state.map, randoms);
+                map.try_wild_battle(&self.data, &mut state.map, trainer, randoms);
 
                 for (id, npc) in map.npcs.iter() {
                     if let Some(trainer) = &npc.trainer {
@@ -264,16 +927,48 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         }
     }
 
-    fn stop_player<R: Rng, P, B>(
+    fn stop_player<R: Rng>(
         &self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &mut Trainer<P, B>,
+        trainer: &mut InitTrainer,
     ) {
         state.map.player.character.stop_move();
 
         if let Some(map) = self.data.maps.get(&state.map.location) {
-            if let Some(destination) = map.warp_at(&state.map.player.character.position.coords) {
+            let player = &mut state.map.player;
+            let tile = map.tile(player.character.position.coords).and_then(|tile| {
+                self.data
+                    .palettes
+                    .get(tile.palette(&map.palettes))
+                    .map(|data| (tile.id(), data))
+            });
+            // spinning keeps going across normal tiles
+            let spinning = player
+                .forced
+                .filter(|forced| matches!(forced, ForcedMovement::Spin(..)));
+            player.forced = match tile {
+                Some((tile, data)) if data.stops.contains(&tile) => None,
+                Some((tile, data)) => data
+                    .forced(&tile, player.character.position.direction)
+                    .or(spinning),
+                None => spinning,
+            };
+        }
+
+        let repel = &mut state.map.player.battle.repel;
+        if *repel != 0 {
+            *repel -= 1;
+            if *repel == 0 {
+                state.map.player.character.input_lock.increment();
+                state.map.message = Self::message("REPEL's effect wore off...");
+            }
+        }
+
+        if let Some(map) = self.data.maps.get(&state.map.location) {
+            if let Some(destination) =
+                map.warp_at(&state.map.player.character.position.coordinate3d())
+            {
                 // Warping does not trigger tile actions!
                 state.map.warp = Some(*destination);
             } else if map.in_bounds(state.map.player.character.position.coords) {
@@ -289,12 +984,24 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         randoms: &mut WorldRandoms<R>,
         delta: f32,
     ) {
+        state.map.clock.update(delta);
+        if let Some(MapPrompt {
+            kind,
+            answer: Some(answer),
+        }) = state.map.prompt
+        {
+            state.map.prompt = None;
+            self.on_prompt(&mut state.map, trainer, kind, answer);
+        }
         if let Some(result) = state.map.player.update(&mut state.map.message, delta) {
             match result {
                 DoMoveResult::Finished => self.stop_player(state, randoms, trainer),
-                DoMoveResult::Interact => self.try_interact(&mut state.map),
+                DoMoveResult::Interact => self.try_interact(&mut state.map, trainer, randoms),
             }
         }
+        self.whiteout(&mut state.map);
+        self.slide_downhill(&mut state.map);
+        self.force_movement(&mut state.map);
         self.move_npcs(&mut state.map, randoms, delta);
         self.scripting.update(
             &self.data,
@@ -306,6 +1013,54 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         // self.update_interactions(player);
     }
 
+    /// Keeps the player moving while tiles are forcing them to.
+    fn force_movement(&self, state: &mut MapState) {
+        let direction = match state.player.forced {
+            Some(ForcedMovement::Slide(direction) | ForcedMovement::Spin(direction)) => direction,
+            None => return,
+        };
+        let character = &state.player.character;
+        if character.moving()
+            || character.locked()
+            || character.input_lock.active()
+            || state.warp.is_some()
+        {
+            return;
+        }
+        self.try_move_player(state, direction);
+        // stop at obstacles
+        if !state.player.character.moving() && state.warp.is_none() {
+            state.player.forced = None;
+        }
+    }
+
+    /// Pulls a cyclist down slopes when they are not moving on their own.
+    fn slide_downhill(&self, state: &mut MapState) {
+        let character = &state.player.character;
+        if character.activity != Activity::Cycling
+            || character.moving()
+            || character.locked()
+            || character.input_lock.active()
+        {
+            return;
+        }
+        if let Some(map) = self.data.maps.get(&state.location) {
+            if map.settings.force_cycling
+                && map
+                    .tile(character.position.coords)
+                    .and_then(|tile| {
+                        self.data
+                            .palettes
+                            .get(tile.palette(&map.palettes))
+                            .map(|data| data.slopes.contains(&tile.id()))
+                    })
+                    .unwrap_or_default()
+            {
+                self.try_move_player(state, Direction::Down);
+            }
+        }
+    }
+
     pub fn try_move_player(&self, state: &mut MapState, direction: Direction) {
         state.player.character.on_try_move(direction);
 
@@ -315,7 +1070,11 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         if let Some(map) = self.get(&state.location) {
             // Check for warp on tile
             if state.warp.is_none() {
-                if let Some(destination) = map.warp_at(&coords) {
+                let position = Coordinate3d {
+                    xy: coords,
+                    z: state.player.character.position.elevation,
+                };
+                if let Some(destination) = map.warp_at(&position) {
                     state.warp = Some(*destination);
                     state.events.push(MapEvent::BeginWarpTransition(coords));
                     return;
@@ -344,20 +1103,44 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
                     .capabilities
                     .contains(&CharacterState::NOCLIP)
             {
-                state.events.push(MapEvent::PlayerJump);
+                // the tile past the ledge must be free to land on
+                let elevation = state.player.character.position.elevation;
+                if matches!(
+                    map.local_movement(coords + offset, state),
+                    Some(code) if code != Elevation::WATER && elevation.can_move(code)
+                ) {
+                    state
+                        .player
+                        .character
+                        .actions
+                        .queue
+                        .push(ActionQueue::Jump(direction));
+                }
                 return;
             }
 
             match map.chunk_movement(coords, state) {
                 MapMovementResult::Option(code) => {
-                    with_code(&mut state.player, code.unwrap_or(1), direction);
+                    step(
+                        &mut state.player,
+                        &mut state.events,
+                        map.music,
+                        code.unwrap_or(1),
+                        direction,
+                    );
                 }
                 MapMovementResult::Chunk(direction, offset, connection) => {
                     if let Some((location, coords, code)) = self
                         .data
                         .connection_movement(direction, offset, connection, state)
                     {
-                        if with_code(&mut state.player, code, direction) {
+                        if step(
+                            &mut state.player,
+                            &mut state.events,
+                            map.music,
+                            code,
+                            direction,
+                        ) {
                             state.player.character.position.coords = coords;
                             state.location = location;
                             self.on_map_change(state);
@@ -366,52 +1149,13 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
                 }
             }
         }
-
-        fn with_code(player: &mut PlayerCharacter, code: MovementId, direction: Direction) -> bool {
-            if Elevation::can_move(player.character.position.elevation, code)
-                || player
-                    .character
-                    .capabilities
-                    .contains(&CharacterState::NOCLIP)
-            {
-                if Elevation::WATER == code {
-                    if player.character.activity != Activity::Swimming {
-                        if player
-                            .character
-                            .capabilities
-                            .contains(&CharacterState::SWIM)
-                            || player
-                                .character
-                                .capabilities
-                                .contains(&CharacterState::NOCLIP)
-                        {
-                            player.character.activity = Activity::Swimming;
-                        } else {
-                            return false;
-                        }
-                    }
-                } else if player.character.activity == Activity::Swimming {
-                    player.character.activity = Activity::Walking;
-                }
-                Elevation::change(&mut player.character.position.elevation, code);
-                player
-                    .character
-                    .actions
-                    .queue
-                    .push(ActionQueue::Move(direction));
-                return true;
-                // self.player.offset =
-                //     direction.pixel_offset(self.player.speed() * 60.0 * delta);
-            }
-            false
-        }
     }
 
-    pub fn warp<R: Rng, P, B>(
+    pub fn warp<R: Rng>(
         &self,
         state: &mut WorldState<S>,
         randoms: &mut WorldRandoms<R>,
-        trainer: &Trainer<P, B>,
+        trainer: &InitTrainer,
         destination: WarpDestination,
     ) {
         if self.data.warp(&mut state.map, destination) {
@@ -419,3 +1163,77 @@ impl<S: WorldScriptingEngine> WorldMapManager<S> {
         }
     }
 }
+
+/// Moves the player onto a tile with the given movement code, getting off the water if it is land.
+fn step(
+    player: &mut PlayerCharacter,
+    events: &mut Vec<MapEvent>,
+    music: MusicId,
+    code: MovementId,
+    direction: Direction,
+) -> bool {
+    if Elevation::can_move(player.character.position.elevation, code)
+        || player
+            .character
+            .capabilities
+            .contains(&CharacterState::NOCLIP)
+    {
+        if Elevation::WATER == code {
+            if player.character.activity != Activity::Swimming {
+                // Getting on the water is done by interacting with it
+                if player
+                    .character
+                    .capabilities
+                    .contains(&CharacterState::NOCLIP)
+                {
+                    player.character.activity = Activity::Swimming;
+                } else {
+                    return false;
+                }
+            }
+        } else if player.character.activity == Activity::Swimming {
+            player.character.activity = Activity::Walking;
+            Elevation::change(&mut player.character.position.elevation, code);
+            events.push(MapEvent::PlayerHop);
+            events.push(MapEvent::PlayMusic(Some(music)));
+            return true;
+        }
+        Elevation::change(&mut player.character.position.elevation, code);
+        player
+            .character
+            .actions
+            .queue
+            .push(ActionQueue::Move(direction));
+        return true;
+        // self.player.offset =
+        //     direction.pixel_offset(self.player.speed() * 60.0 * delta);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_water_plays_map_music() {
+        let music: MusicId = "route_1".parse().unwrap();
+        let mut player = PlayerCharacter::default();
+        let mut events = Vec::new();
+        player.character.activity = Activity::Swimming;
+        Elevation::change(&mut player.character.position.elevation, Elevation::WATER);
+
+        assert!(step(
+            &mut player,
+            &mut events,
+            music,
+            Elevation::HL1,
+            Direction::Up
+        ));
+        assert_eq!(player.character.activity, Activity::Walking);
+        assert!(matches!(
+            events.last(),
+            Some(MapEvent::PlayMusic(Some(id))) if id == &music
+        ));
+    }
+}
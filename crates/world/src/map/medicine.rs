@@ -0,0 +1,70 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use pokedex::{
+    ailment::Ailment,
+    item::ItemId,
+    pokemon::{owned::OwnedPokemon, Health},
+};
+
+/// Items that can be used on party members outside of battle.
+pub type MedicineData = HashMap<ItemId, Medicine>;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Medicine {
+    /// Restores HP, or all of it if no amount is given
+    Heal(Option<Health>),
+    /// Cures a status condition, or any of them if none is given
+    Cure(Option<Ailment>),
+    /// Restores all HP and cures any status condition
+    Restore,
+    /// Revives a fainted pokemon with a fraction of its HP
+    Revive(f32),
+}
+
+impl Medicine {
+    /// Uses the medicine on a pokemon. Returns a message if it had any effect.
+    pub fn apply(&self, pokemon: &mut OwnedPokemon) -> Option<String> {
+        let max = pokemon.max_hp();
+        match self {
+            Self::Heal(amount) => {
+                if pokemon.fainted() || pokemon.hp >= max {
+                    return None;
+                }
+                let hp = pokemon.hp;
+                pokemon.hp = amount
+                    .map(|amount| hp.saturating_add(amount).min(max))
+                    .unwrap_or(max);
+                Some(format!(
+                    "{}'s HP was restored by {} point(s).",
+                    pokemon.name(),
+                    pokemon.hp - hp
+                ))
+            }
+            Self::Cure(cures) => {
+                let ailment = pokemon.ailment.as_ref().map(|live| live.ailment)?;
+                if pokemon.fainted() || matches!(cures, Some(cures) if cures != &ailment) {
+                    return None;
+                }
+                pokemon.ailment = None;
+                Some(format!("{} was cured of {:?}.", pokemon.name(), ailment))
+            }
+            Self::Restore => {
+                if pokemon.fainted() || (pokemon.hp >= max && pokemon.ailment.is_none()) {
+                    return None;
+                }
+                pokemon.hp = max;
+                pokemon.ailment = None;
+                Some(format!("{}'s HP was restored.", pokemon.name()))
+            }
+            Self::Revive(fraction) => {
+                if !pokemon.fainted() {
+                    return None;
+                }
+                pokemon.hp = ((max as f32 * fraction) as Health).clamp(1, max);
+                pokemon.ailment = None;
+                Some(format!("{}'s HP was restored.", pokemon.name()))
+            }
+        }
+    }
+}
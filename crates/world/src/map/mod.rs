@@ -1,9 +1,12 @@
+use hashbrown::HashSet;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use pokedex::trainer::InitTrainer;
+
 use crate::{
     character::{npc::Npcs, Activity},
-    positions::{Coordinate, CoordinateInt, Direction, Location},
+    positions::{Coordinate, Coordinate3d, CoordinateInt, Direction, Location},
     random::WorldRandoms,
     state::map::MapState,
 };
@@ -11,9 +14,9 @@ use crate::{
 use self::{
     chunk::WorldChunk,
     movement::MapMovementResult,
-    object::Objects,
+    object::{MapObject, ObjectId, Objects},
     warp::{WarpDestination, Warps},
-    wild::WildEntries,
+    wild::{EncounterModifiers, WildEntries, WildEntry, WildType},
 };
 
 pub mod data;
@@ -24,6 +27,9 @@ pub mod tile;
 
 pub mod chunk;
 
+pub mod evolution;
+pub mod machine;
+pub mod medicine;
 pub mod object;
 pub mod warp;
 pub mod wild;
@@ -65,7 +71,11 @@ pub struct WorldMap {
     pub wild: Option<WildEntries>,
 
     pub npcs: Npcs,
-    // pub objects: Objects,
+    #[serde(default)]
+    pub objects: Objects,
+    /// Tiles the player can use a PC from
+    #[serde(default)]
+    pub pcs: HashSet<Coordinate>,
     // pub items: Items,
     // pub signs: Signs,
 
@@ -82,8 +92,21 @@ pub struct WorldMapSettings {
     pub fly_position: Option<Coordinate>,
     #[serde(default)]
     pub brightness: Brightness,
+    #[serde(default)]
+    pub weather: Weather,
     #[serde(default = "WorldMapSettings::default_transition")]
     pub transition: TransitionId,
+    #[serde(default)]
+    pub kind: MapKind,
+    /// Where the map is shown on the town map
+    #[serde(default)]
+    pub region: Option<RegionSection>,
+    /// If the player can ride a bicycle here
+    #[serde(default = "crate::const_true")]
+    pub cycling: bool,
+    /// Keeps the player on their bicycle, like on Cycling Road
+    #[serde(default)]
+    pub force_cycling: bool,
 }
 
 impl WorldMap {
@@ -110,6 +133,10 @@ impl WorldMap {
         self.movements
             .get(coords.x as usize + coords.y as usize * self.width as usize)
             .map(|code| {
+                let mut objects = self
+                    .objects
+                    .iter()
+                    .filter(|(.., object)| object.group.is_some());
                 match state.entities.get(&self.id) {
                     Some(entities) => {
                         // Iterators
@@ -118,23 +145,24 @@ impl WorldMap {
                             .values()
                             .filter(|character| !character.hidden)
                             .map(|character| character.position.coords);
-                        // let objects = entities
-                        //     .objects
-                        //     .values()
-                        //     .filter(|object| !object.removed)
-                        //     .map(|object| object.entity.coordinate);
+                        let objects = objects
+                            .filter(|(id, ..)| !entities.is_removed(id))
+                            .map(|(.., object)| object.coordinate);
                         // let items = entities
                         //     .items
                         //     .values()
                         //     .filter(|object| !object.entity.data.hidden || !object.removed)
                         //     .map(|object| object.entity.coordinate);
                         // find used locations
-                        match npcs./*chain(objects).chain(items).*/any(|c| c == coords) {
+                        match npcs.chain(objects)./*chain(items).*/any(|c| c == coords) {
                             true => 1,
                             false => *code,
                         }
                     }
-                    None => *code,
+                    None => match objects.any(|(.., object)| object.coordinate == coords) {
+                        true => 1,
+                        false => *code,
+                    },
                 }
             })
     }
@@ -178,18 +206,18 @@ impl WorldMap {
         self.unbounded_movement(coords, state).into()
     }
 
-    pub fn warp_at(&self, coordinate: &Coordinate) -> Option<&WarpDestination> {
+    pub fn warp_at(&self, coordinate: &Coordinate3d) -> Option<&WarpDestination> {
         self.warps
             .iter()
-            .find(|warp| warp.area.contains(coordinate))
+            .find(|warp| warp.area.contains(&coordinate.xy) && warp.elevation.matches(coordinate.z))
             .map(|entry| &entry.destination)
     }
 
-    // pub fn object_at(&self, coordinate: &Coordinate) -> Option<&ObjectEntity> {
-    //     self.objects
-    //         .values()
-    //         .find(|object| &object.coordinate == coordinate)
-    // }
+    pub fn object_at(&self, coordinate: &Coordinate) -> Option<(&ObjectId, &MapObject)> {
+        self.objects
+            .iter()
+            .find(|(.., object)| object.group.is_some() && &object.coordinate == coordinate)
+    }
 
     // pub fn item_at(&self, coordinate: &Coordinate) -> Option<&ItemEntity> {
     //     self.items
@@ -222,9 +250,9 @@ impl WorldMap {
         &self,
         data: &data::WorldMapData,
         state: &mut MapState,
+        trainer: &InitTrainer,
         randoms: &mut WorldRandoms<R>,
     ) {
-        use wild::*;
         if let Some(current) = self.tile(state.player.character.position.coords) {
             if data
                 .palettes
@@ -236,13 +264,26 @@ impl WorldMap {
                     Activity::Swimming => &WildType::Water,
                     _ => &WildType::Land,
                 };
-                if let Some(entry) = &self.wild.as_ref().map(|entries| entries.get(t)).flatten() {
-                    if let Some(entry) =
-                        WildEntry::generate(&data.wild, t, entry, &mut randoms.wild)
-                    {
-                        state.player.battle.battling = Some(entry);
-                    }
-                }
+                let modifiers = data.encounter_modifiers(state, trainer);
+                self.wild_battle(data, state, randoms, t, &modifiers);
+            }
+        }
+    }
+
+    /// Rolls the encounter table of the given type using its own ratio.
+    pub fn wild_battle<R: Rng>(
+        &self,
+        data: &data::WorldMapData,
+        state: &mut MapState,
+        randoms: &mut WorldRandoms<R>,
+        t: &WildType,
+        modifiers: &EncounterModifiers,
+    ) {
+        if let Some(entry) = self.wild.as_ref().map(|entries| entries.get(t)).flatten() {
+            if let Some(entry) =
+                WildEntry::generate(&data.wild, t, entry, &mut randoms.wild, modifiers)
+            {
+                state.player.battle.battling = Some(entry);
             }
         }
     }
@@ -259,7 +300,12 @@ impl Default for WorldMapSettings {
         Self {
             fly_position: Default::default(),
             brightness: Default::default(),
+            weather: Default::default(),
             transition: Self::default_transition(),
+            kind: Default::default(),
+            region: Default::default(),
+            cycling: true,
+            force_cycling: false,
         }
     }
 }
@@ -272,15 +318,55 @@ pub enum Brightness {
     // FlashNeeded,
 }
 
-// #[deprecated]
-// #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
-// pub enum MapIcon {
-//     City(u8, u8),
-//     Route(u8, u8, u8, u8),
-// }
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    Sunny,
+    Rain,
+    Thunderstorm,
+    Fog,
+    Ash,
+    Shade,
+    Sandstorm,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum MapKind {
+    Outdoor,
+    Indoor,
+    /// Can be escaped from with Escape Rope or Dig
+    Dungeon,
+}
+
+/// An area of the town map, in tiles
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct RegionSection {
+    pub x: u8,
+    pub y: u8,
+    pub width: u8,
+    pub height: u8,
+}
+
+impl RegionSection {
+    pub fn contains(&self, x: u8, y: u8) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
 
 impl Default for Brightness {
     fn default() -> Self {
         Self::Day
     }
 }
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self::Clear
+    }
+}
+
+impl Default for MapKind {
+    fn default() -> Self {
+        Self::Outdoor
+    }
+}
@@ -38,6 +38,11 @@ impl Elevation {
         }
     }
 
+    /// Events at elevation 0 happen at any elevation.
+    pub const fn matches(self, other: Self) -> bool {
+        self.0 == 0 || self.0 == other.0
+    }
+
     pub fn change(&mut self, code: MovementId) {
         if code % 4 == 0 {
             *self = Self(code >> 2);
@@ -9,10 +9,7 @@ use serde::{Deserialize, Serialize};
 pub mod group;
 pub use group::*;
 
-use crate::{
-    positions::{Coordinate, Location},
-    // state::map::MapState,
-};
+use crate::positions::Coordinate;
 
 pub type ObjectId = u16;
 pub type ObjectType = tinystr::TinyAsciiStr<4>;
@@ -30,52 +27,6 @@ pub struct MapObject {
 
 pub enum Removable {}
 
-// impl ObjectEntity {
-//     const TREE: &'static ObjectType =
-//         unsafe { &ObjectType::from_bytes_unchecked(1701147252u32.to_ne_bytes()) };
-
-//     const CUT: &'static MoveId = unsafe {
-//         &MoveId(tinystr::TinyStr16::from_bytes_unchecked(
-//             7632227u128.to_ne_bytes(),
-//         ))
-//     };
-
-//     const ROCK: &'static ObjectType =
-//         unsafe { &ObjectType::from_bytes_unchecked(1801678706u32.to_ne_bytes()) };
-//     /// "rock-smash"
-//     const ROCK_SMASH: &'static MoveId = unsafe {
-//         &MoveId(tinystr::TinyStr16::from_bytes_unchecked(
-//             493254510180952753532786u128.to_ne_bytes(),
-//         ))
-//     };
-
-//     pub fn try_break<
-//     >(
-//         location: &Location,
-//         coordinate: Coordinate,
-//         group: &ObjectType,
-//         trainer: &mut InitTrainer,
-//         state: &mut MapState,
-//         force: bool,
-//     ) {
-//         let id = match group {
-//             Self::ROCK => Self::ROCK_SMASH,
-//             Self::TREE => Self::CUT,
-//             _ => return,
-//         };
-//         if trainer
-//             .party
-//             .iter()
-//             .any(|p| p.moves.iter().any(|m| m.id() == id))
-//             || force
-//         {
-//             if let Some(objects) = state.entities.get_mut(location) {
-//                 todo!()
-//             }
-//         }
-//     }
-// }
-
 // impl ItemEntity {
 //     pub fn pickup(&self, location: &Location, coordinate: Coordinate, state: &mut MapState) {
 //         // if !state.contains_object(location, &coordinate) {
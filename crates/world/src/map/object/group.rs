@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 use pokedex::moves::MoveId;
 
+use crate::{character::Capability, map::wild::WildType};
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct ObjectGroup {
     pub solid: bool,
@@ -12,9 +14,13 @@ pub struct ObjectGroup {
 pub struct ObjectDestroy {
     pub regeneratable: bool,
     pub method: DestroyMethod,
+    /// Wild encounter table to roll once the object is destroyed (i.e. rock smash)
+    #[serde(default)]
+    pub wild: Option<WildType>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum DestroyMethod {
     Move(MoveId),
+    Capability(Capability),
 }
@@ -1,4 +1,7 @@
-use crate::positions::{BoundingBox, Destination, Location};
+use crate::{
+    map::movement::Elevation,
+    positions::{BoundingBox, Destination, Location},
+};
 use serde::{Deserialize, Serialize};
 
 // pub type WarpId = tinystr::TinyStr16;
@@ -8,6 +11,8 @@ pub type Warps = Vec<WarpEntry>;
 #[serde(deny_unknown_fields)]
 pub struct WarpEntry {
     pub area: BoundingBox,
+    #[serde(default)]
+    pub elevation: Elevation,
     pub destination: WarpDestination,
 }
 
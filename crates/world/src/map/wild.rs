@@ -3,7 +3,12 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 
-use pokedex::pokemon::{owned::SavedPokemon, party::Party, Level, PokemonId};
+use pokedex::{
+    item::ItemId,
+    pokemon::{owned::SavedPokemon, party::Party, Level, PokemonId},
+};
+
+use crate::state::clock::TimeOfDay;
 
 use super::battle::{BattleEntry, BattleId};
 
@@ -20,10 +25,47 @@ pub enum WildType {
     Fishing(u8),
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncounterData {
+    /// Lead pokemon abilities that change encounters.
+    /// The dex does not have abilities, so these are listed by species.
+    #[serde(default)]
+    pub abilities: HashMap<PokemonId, EncounterAbility>,
+    /// Held items that multiply the encounter rate
+    #[serde(default)]
+    pub items: HashMap<ItemId, f32>,
+    /// Multiplies every encounter rate, used for testing
+    #[serde(default = "EncounterData::default_multiplier")]
+    pub multiplier: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EncounterAbility {
+    Stench,
+    Illuminate,
+    Intimidate,
+    KeenEye,
+}
+
+/// Changes how often and which wild pokemon are encountered.
+#[derive(Debug, Clone, Copy)]
+pub struct EncounterModifiers {
+    pub rate: f32,
+    /// Pokemon below this level are not encountered (Repel)
+    pub min_level: Option<Level>,
+    /// Pokemon at or below this level are skipped half of the time (Intimidate, Keen Eye)
+    pub weak_level: Option<Level>,
+    pub time: TimeOfDay,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WildEntry {
     pub ratio: Ratio,
     pub encounters: Vec<WildPokemon>,
+    /// Replaces the encounters during a time of day
+    #[serde(default)]
+    pub times: HashMap<TimeOfDay, Vec<WildPokemon>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,9 +74,44 @@ pub struct WildPokemon {
     pub levels: RangeInclusive<Level>,
 }
 
+impl EncounterData {
+    pub fn default_multiplier() -> f32 {
+        1.0
+    }
+}
+
+impl Default for EncounterData {
+    fn default() -> Self {
+        Self {
+            abilities: Default::default(),
+            items: Default::default(),
+            multiplier: Self::default_multiplier(),
+        }
+    }
+}
+
+impl Default for EncounterModifiers {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            min_level: None,
+            weak_level: None,
+            time: Default::default(),
+        }
+    }
+}
+
 impl WildEntry {
-    pub fn should_encounter(&self, random: &mut impl Rng) -> bool {
-        random.gen_range(Ratio::MIN..Ratio::MAX) < self.ratio
+    /// Every species that can appear, at any time of day.
+    pub fn species(&self) -> impl Iterator<Item = PokemonId> + '_ {
+        self.encounters
+            .iter()
+            .chain(self.times.values().flatten())
+            .map(|pokemon| pokemon.species)
+    }
+
+    pub fn should_encounter(&self, random: &mut impl Rng, rate: f32) -> bool {
+        (random.gen_range(Ratio::MIN..Ratio::MAX) as f32) < self.ratio as f32 * rate
     }
 
     pub fn generate(
@@ -42,17 +119,28 @@ impl WildEntry {
         t: &WildType,
         entry: &WildEntry,
         random: &mut impl Rng,
+        modifiers: &EncounterModifiers,
     ) -> Option<BattleEntry> {
-        if entry.should_encounter(random) {
+        if entry.should_encounter(random, modifiers.rate) {
             let chances = match chances.get(t) {
                 Some(chances) => chances,
                 None => return None,
             };
-            let pokemon = match entry.encounters.get(encounter_index(chances, random)) {
+            let encounters = entry
+                .times
+                .get(&modifiers.time)
+                .unwrap_or(&entry.encounters);
+            let pokemon = match encounters.get(encounter_index(chances, random)) {
                 Some(pokemon) => pokemon,
                 None => return None,
             };
             let level = random.gen_range(pokemon.levels.clone());
+            if matches!(modifiers.min_level, Some(min) if level < min) {
+                return None;
+            }
+            if matches!(modifiers.weak_level, Some(weak) if level <= weak) && random.gen_bool(0.5) {
+                return None;
+            }
             let pokemon = SavedPokemon {
                 pokemon: pokemon.species,
                 level,
@@ -2,14 +2,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     map::movement::Elevation,
-    positions::{Coordinate, Destination, Direction},
+    positions::{Coordinate, Coordinate3d, Destination, Direction},
 };
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct Position {
     pub coords: Coordinate,
     pub direction: Direction,
-    #[deprecated]
     #[serde(default)]
     pub elevation: Elevation,
 }
@@ -29,6 +28,13 @@ impl Position {
         }
     }
 
+    pub fn coordinate3d(&self) -> Coordinate3d {
+        Coordinate3d {
+            xy: self.coords,
+            z: self.elevation,
+        }
+    }
+
     pub fn forwards(&self) -> Coordinate {
         self.coords.in_direction(self.direction)
     }
@@ -1,5 +1,8 @@
 use audio::{SoundId, SoundVariant};
-use pokedex::item::ItemId;
+use pokedex::{
+    item::ItemId,
+    pokemon::{Level, PokemonId},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{map::object::ObjectId, positions::Direction, character::npc::NpcMovement};
@@ -66,6 +69,9 @@ pub enum WorldInstruction {
     /// Npc walks in a direction
     Walk(Direction),
 
+    /// Npc jumps two tiles in a direction
+    Jump(Direction),
+
     Look(Direction),
 
     /// Start trainer battle
@@ -87,4 +93,7 @@ pub enum WorldInstruction {
     AddItem(ItemId),
     CheckItemSpace(String, i32), //ItemId)
     GetItemName(i32, String),
+
+    /// Give player a pokemon, which is sent to the PC if their party is full
+    GivePokemon(PokemonId, Level),
 }
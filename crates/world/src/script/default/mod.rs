@@ -1,4 +1,4 @@
-use firecore_pokedex::trainer::InitTrainer;
+use firecore_pokedex::{pokemon::owned::SavedPokemon, trainer::InitTrainer};
 use firecore_text::{MessagePage, MessageState, MessageStates};
 use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
@@ -10,11 +10,14 @@ use crate::{
         npc::{Npc, NpcId},
         DoMoveResult,
     },
-    map::{battle::BattleEntry, data::WorldMapData},
+    map::{battle::BattleEntry, data::WorldMapData, movement::Elevation},
     message::MessageTheme,
-    positions::{Coordinate, Location},
+    positions::{Coordinate3d, Location},
     random::WorldRandoms,
-    state::map::{MapEvent, MapState},
+    state::{
+        clock::TimeOfDay,
+        map::{MapEvent, MapState},
+    },
 };
 
 use super::WorldScriptingEngine;
@@ -76,10 +79,22 @@ pub struct DefaultWorldScriptEngine {
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScriptLocation {
-    pub tiles: HashMap<Coordinate, ScriptId>,
+    pub tiles: HashMap<Coordinate3d, ScriptId>,
     pub npcs: HashMap<NpcId, ScriptId>,
 }
 
+impl ScriptLocation {
+    /// Gets the tile script at a position, falling back to one at elevation 0.
+    pub fn tile(&self, position: &Coordinate3d) -> Option<&ScriptId> {
+        self.tiles.get(position).or_else(|| {
+            self.tiles.get(&Coordinate3d {
+                xy: position.xy,
+                z: Elevation(0),
+            })
+        })
+    }
+}
+
 impl WorldScriptingEngine for DefaultWorldScriptEngine {
     type State = DefaultScriptState;
 
@@ -93,7 +108,7 @@ impl WorldScriptingEngine for DefaultWorldScriptEngine {
         if let Some(scriptid) = self
             .locations
             .get(&world.location)
-            .and_then(|location| location.tiles.get(&world.player.character.position.coords))
+            .and_then(|location| location.tile(&world.player.character.position.coordinate3d()))
         {
             self.run(world, state, scriptid, None);
         }
@@ -179,6 +194,19 @@ impl WorldScriptingEngine for DefaultWorldScriptEngine {
                                 },
                             );
                         }
+                        "GetTimeOfDay" => {
+                            variables.insert(
+                                name.clone(),
+                                match world.clock.time_of_day() {
+                                    TimeOfDay::Morning => 0,
+                                    TimeOfDay::Day => 1,
+                                    TimeOfDay::Night => 2,
+                                },
+                            );
+                        }
+                        "GetHour" => {
+                            variables.insert(name.clone(), world.clock.hour() as _);
+                        }
                         _ => (),
                     }
                     queue.remove(0);
@@ -274,6 +302,14 @@ impl WorldScriptingEngine for DefaultWorldScriptEngine {
                     log::warn!("Add GetItemName instruction!");
                     queue.remove(0);
                 }
+                WorldInstruction::GivePokemon(pokemon, level) => {
+                    world.events.push(MapEvent::GivePokemon(SavedPokemon {
+                        pokemon: *pokemon,
+                        level: *level,
+                        ..Default::default()
+                    }));
+                    queue.remove(0);
+                }
                 // WorldInstruction::Walk(..) | WorldInstruction::FacePlayer | WorldInstruction::TrainerBattleSingle | WorldInstruction::Msgbox(..) | WorldInstruction::Look(..) => {
                 npc_inst => {
                     if let Some((map, settings, npc)) = state
@@ -472,6 +508,29 @@ impl WorldScriptingEngine for DefaultWorldScriptEngine {
                                     }
                                 }
                             }
+                            WorldInstruction::Jump(direction) => {
+                                if let Some(character) =
+                                    world.entities.get_mut(&world.location).and_then(|states| {
+                                        states.npcs.get_mut(state.executor.as_ref().unwrap())
+                                    })
+                                {
+                                    if !character.moving() {
+                                        match state.flags.contains("TEMP_0") {
+                                            true => {
+                                                state.flags.remove("TEMP_0");
+                                                queue.remove(0);
+                                            }
+                                            false => {
+                                                character
+                                                    .actions
+                                                    .queue
+                                                    .push(ActionQueue::Jump(*direction));
+                                                state.flags.insert("TEMP_0".to_owned());
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                             _ => unreachable!(),
                         }
                     }
@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::script::WorldScriptingEngine;
 
+pub mod clock;
+pub mod dex;
 pub mod map;
+pub mod storage;
 
 // pub type SavedWorldState<R, S> = WorldState<R, S, SavedPokemon, SavedBag>;
 // pub type InitWorldState<R, S, P, M, I> = WorldState<R, S, OwnedPokemon, Bag<I>>;
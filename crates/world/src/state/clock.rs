@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// In-game clock, counted in seconds since midnight.
+///
+/// The clock only advances while the game is being played, scaled by
+/// [`WorldClock::speed`]. It does not follow the system's wall clock.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldClock {
+    #[serde(default = "WorldClock::default_time")]
+    pub time: f64,
+    /// In-game seconds that pass every real second.
+    /// A speed of 1 follows real time.
+    #[serde(default = "WorldClock::default_speed")]
+    pub speed: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimeOfDay {
+    Morning,
+    Day,
+    Night,
+}
+
+impl WorldClock {
+    pub const DAY: f64 = 24.0 * 60.0 * 60.0;
+    pub const HOUR: f64 = 60.0 * 60.0;
+
+    pub fn default_time() -> f64 {
+        12.0 * Self::HOUR
+    }
+
+    pub fn default_speed() -> f32 {
+        60.0
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.time = (self.time + delta as f64 * self.speed as f64).rem_euclid(Self::DAY);
+    }
+
+    pub fn set_hour(&mut self, hour: u8) {
+        self.time = (hour as f64 * Self::HOUR).rem_euclid(Self::DAY);
+    }
+
+    pub fn hour(&self) -> u8 {
+        (self.time / Self::HOUR) as u8
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        TimeOfDay::from(self.hour())
+    }
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            time: Self::default_time(),
+            speed: Self::default_speed(),
+        }
+    }
+}
+
+impl From<u8> for TimeOfDay {
+    fn from(hour: u8) -> Self {
+        match hour {
+            4..=9 => Self::Morning,
+            10..=19 => Self::Day,
+            _ => Self::Night,
+        }
+    }
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self::Day
+    }
+}
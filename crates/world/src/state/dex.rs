@@ -0,0 +1,32 @@
+use hashbrown::HashSet;
+use pokedex::pokemon::PokemonId;
+use serde::{Deserialize, Serialize};
+
+/// Species the player has seen and caught.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PlayerDex {
+    #[serde(default)]
+    pub seen: HashSet<PokemonId>,
+    #[serde(default)]
+    pub caught: HashSet<PokemonId>,
+}
+
+impl PlayerDex {
+    pub fn see(&mut self, pokemon: PokemonId) {
+        self.seen.insert(pokemon);
+    }
+
+    /// Catching a pokemon also counts as seeing it.
+    pub fn catch(&mut self, pokemon: PokemonId) {
+        self.seen.insert(pokemon);
+        self.caught.insert(pokemon);
+    }
+
+    pub fn seen(&self, pokemon: &PokemonId) -> bool {
+        self.seen.contains(pokemon)
+    }
+
+    pub fn caught(&self, pokemon: &PokemonId) -> bool {
+        self.caught.contains(pokemon)
+    }
+}
@@ -1,14 +1,16 @@
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use audio::{SoundId, SoundVariant};
+use pokedex::{moves::MoveId, pokemon::owned::SavedPokemon};
 use text::MessageStates;
 
 use crate::{
     character::{npc::NpcId, player::PlayerCharacter, CharacterState},
     map::{
-        data::{WorldMapData},
+        data::WorldMapData,
         movement::Elevation,
+        object::{ObjectId, ObjectType},
         warp::WarpDestination,
         MusicId,
     },
@@ -16,8 +18,17 @@ use crate::{
     positions::{Coordinate, Location, Spot},
 };
 
+use super::clock::WorldClock;
+
 pub type MapMessage = MessageStates<MessageColor, MessageTheme>;
 
+/// Holds the string "cycling"
+pub const CYCLING_MUSIC: MusicId = unsafe {
+    MusicId::from_bytes_unchecked([
+        0x63, 0x79, 0x63, 0x6C, 0x69, 0x6E, 0x67, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ])
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapState {
     /// The currently loaded map
@@ -36,12 +47,19 @@ pub struct MapState {
     #[serde(default)]
     pub places: GlobalPlacesData,
     #[serde(default)]
+    pub clock: WorldClock,
+    #[serde(default)]
     pub npc: GlobalNpcData,
     #[serde(default)]
     pub warp: Option<WarpDestination>,
 
     #[serde(default)]
     pub message: MapMessage,
+    #[serde(default)]
+    pub prompt: Option<MapPrompt>,
+    /// Part of the whiteout sequence the player is in
+    #[serde(default)]
+    pub whiteout: Option<Whiteout>,
 
     #[serde(default)]
     pub debug_mode: bool,
@@ -52,17 +70,56 @@ pub enum MapEvent {
     PlayMusic(Option<MusicId>),
     PlaySound(SoundId, SoundVariant),
     BeginWarpTransition(Coordinate),
-    PlayerJump,
+    /// Hop one tile forward (onto or off of water)
+    PlayerHop,
+    BreakObject(Coordinate, ObjectType),
+    /// Opens the town map, letting the player fly if true
+    OpenTownMap(bool),
+    /// Opens the PC's pokemon storage
+    OpenPc,
+    /// Adds a pokemon to the party, or the PC if the party is full
+    GivePokemon(SavedPokemon),
+    /// Teaches a move to the party member at the index, asking which move to forget if needed
+    LearnMove(usize, MoveId),
+    /// Fishes with the rod in front of the player
+    Fish(u8),
     // Battle(BattleEntry),
     // Command(PlayerActions),
 }
 
+/// A yes/no question shown under the current message.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MapPrompt {
+    pub kind: PromptKind,
+    /// Set by the GUI once the player has answered
+    #[serde(default)]
+    pub answer: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PromptKind {
+    Surf,
+}
+
+/// Steps after the player loses a battle, each waiting on the one before it to finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Whiteout {
+    /// Telling the player they whited out, then warping to where they last healed
+    Warping,
+    /// Waiting for the warp to finish before the player's pokemon are tended to
+    Healing,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct EntityStates {
     #[serde(skip)]
     pub npcs: HashMap<NpcId, CharacterState>,
-    // #[serde(default)]
-    // pub objects: HashMap<ObjectId, ObjectEntityState>,
+    /// Objects on the map that have been destroyed
+    #[serde(default)]
+    pub removed: HashSet<ObjectId>,
+    /// Destroyed objects that come back once the player leaves the map, so they are never saved
+    #[serde(skip)]
+    pub regenerating: HashSet<ObjectId>,
     // #[serde(default)]
     // pub items: HashMap<ObjectId, ItemEntityState>,
 }
@@ -80,6 +137,21 @@ pub struct GlobalPlacesData {
     pub heal: Option<Spot>,
     #[serde(default)]
     pub cave: Option<Spot>,
+    /// Towns the player can fly to
+    #[serde(default)]
+    pub visited: HashSet<Location>,
+}
+
+impl EntityStates {
+    pub fn is_removed(&self, id: &ObjectId) -> bool {
+        self.removed.contains(id) || self.regenerating.contains(id)
+    }
+}
+
+impl MapPrompt {
+    pub fn new(kind: PromptKind) -> Self {
+        Self { kind, answer: None }
+    }
 }
 
 impl MapState {
@@ -90,9 +162,17 @@ impl MapState {
         }
     }
 
-    /// to - do: make better object resetting
+    /// Resets the entities of maps the player is not on. Only objects destroyed for good stay removed.
     pub fn update_objects(&mut self, data: &WorldMapData) {
-        self.entities.retain(|loc, _| loc == &self.location);
+        let location = self.location;
+        self.entities.retain(|loc, entities| {
+            if loc == &location {
+                return true;
+            }
+            entities.npcs.clear();
+            entities.regenerating.clear();
+            !entities.removed.is_empty()
+        });
         if let Some(map) = data.maps.get(&self.location) {
             if !self.entities.contains_key(&self.location) {
                 self.entities.insert(self.location, Default::default());
@@ -160,10 +240,13 @@ impl Default for MapState {
             player: Default::default(),
             events: Default::default(),
             places: Default::default(),
+            clock: Default::default(),
             npc: Default::default(),
             entities: Default::default(),
             warp: Default::default(),
             message: Default::default(),
+            prompt: Default::default(),
+            whiteout: Default::default(),
             debug_mode: Default::default(),
         }
     }
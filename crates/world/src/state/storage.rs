@@ -0,0 +1,222 @@
+use pokedex::pokemon::owned::SavedPokemon;
+use serde::{Deserialize, Serialize};
+
+/// Most pokemon a trainer can carry.
+pub const PARTY_SIZE: usize = 6;
+
+/// Number of boxes in the PC.
+pub const BOXES: usize = 14;
+/// Number of pokemon each box holds.
+pub const BOX_SIZE: usize = 30;
+/// Number of wallpapers a box can use.
+pub const WALLPAPERS: u8 = 16;
+
+/// Pokemon kept in the PC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PokemonStorage {
+    pub boxes: Vec<PokemonBox>,
+    /// The box pokemon are deposited into first
+    #[serde(default)]
+    pub current: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PokemonBox {
+    pub name: String,
+    #[serde(default)]
+    pub wallpaper: u8,
+    pub pokemon: Vec<Option<SavedPokemon>>,
+    #[serde(default)]
+    pub trainers: OriginalTrainers,
+}
+
+/// Trainer ID of the original trainer of the pokemon in each slot.
+/// Pokemon have no field for it, so it is kept beside them and moved along with them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OriginalTrainers(Vec<Option<u16>>);
+
+impl PokemonStorage {
+    /// Puts a pokemon in the first free slot, starting with the current box.
+    /// Returns the box it was put in, or the pokemon if every box is full.
+    pub fn deposit(
+        &mut self,
+        pokemon: SavedPokemon,
+        trainer: Option<u16>,
+    ) -> Result<usize, SavedPokemon> {
+        let count = self.boxes.len();
+        match (0..count)
+            .map(|offset| (self.current + offset) % count)
+            .find_map(|index| {
+                self.boxes[index]
+                    .pokemon
+                    .iter()
+                    .position(Option::is_none)
+                    .map(|slot| (index, slot))
+            }) {
+            Some((index, slot)) => {
+                self.boxes[index].pokemon[slot] = Some(pokemon);
+                self.boxes[index].trainers.set(slot, trainer);
+                Ok(index)
+            }
+            None => Err(pokemon),
+        }
+    }
+
+    pub fn get(&self, index: usize, slot: usize) -> Option<&SavedPokemon> {
+        self.boxes
+            .get(index)
+            .and_then(|b| b.pokemon.get(slot))
+            .and_then(Option::as_ref)
+    }
+
+    /// Takes a pokemon and its original trainer out of its slot. Also used to release pokemon.
+    pub fn take(&mut self, index: usize, slot: usize) -> Option<(SavedPokemon, Option<u16>)> {
+        let b = self.boxes.get_mut(index)?;
+        let pokemon = b.pokemon.get_mut(slot).and_then(Option::take)?;
+        Some((pokemon, b.trainers.take(slot)))
+    }
+
+    /// Puts a pokemon in an empty slot, giving it back if the slot is taken or does not exist.
+    pub fn put(
+        &mut self,
+        index: usize,
+        slot: usize,
+        pokemon: SavedPokemon,
+        trainer: Option<u16>,
+    ) -> Result<(), SavedPokemon> {
+        match self
+            .boxes
+            .get_mut(index)
+            .filter(|b| matches!(b.pokemon.get(slot), Some(None)))
+        {
+            Some(b) => {
+                b.pokemon[slot] = Some(pokemon);
+                b.trainers.set(slot, trainer);
+                Ok(())
+            }
+            None => Err(pokemon),
+        }
+    }
+
+    /// Swaps the contents of two slots.
+    pub fn swap(&mut self, from: (usize, usize), to: (usize, usize)) {
+        if self.get_slot(from).is_some() && self.get_slot(to).is_some() {
+            let a = self.boxes[from.0].pokemon[from.1].take();
+            let b = std::mem::replace(&mut self.boxes[to.0].pokemon[to.1], a);
+            self.boxes[from.0].pokemon[from.1] = b;
+            let a = self.boxes[from.0].trainers.take(from.1);
+            let b = self.boxes[to.0].trainers.take(to.1);
+            self.boxes[to.0].trainers.set(to.1, a);
+            self.boxes[from.0].trainers.set(from.1, b);
+        }
+    }
+
+    fn get_slot(&self, (index, slot): (usize, usize)) -> Option<&Option<SavedPokemon>> {
+        self.boxes.get(index).and_then(|b| b.pokemon.get(slot))
+    }
+
+    /// Fixes storage from a save that was edited by hand,
+    /// giving it every box and slot and moving pokemon out of slots that should not exist.
+    pub fn repair(&mut self) {
+        let defaults = Self::default();
+        if self.boxes.len() < BOXES {
+            let count = self.boxes.len();
+            self.boxes.extend(defaults.boxes.into_iter().skip(count));
+        }
+        let mut overflow = Vec::new();
+        for b in self.boxes.iter_mut() {
+            if b.pokemon.len() > BOX_SIZE {
+                overflow.extend(b.pokemon.drain(BOX_SIZE..).enumerate().filter_map(
+                    |(slot, pokemon)| {
+                        pokemon.map(|pokemon| (pokemon, b.trainers.take(BOX_SIZE + slot)))
+                    },
+                ));
+            }
+            b.pokemon.resize(BOX_SIZE, None);
+        }
+        self.current = self.current.min(self.boxes.len() - 1);
+        for (pokemon, trainer) in overflow {
+            if self.deposit(pokemon, trainer).is_err() {
+                let index = self.boxes.len();
+                let mut b = PokemonBox {
+                    name: format!("BOX {}", index + 1),
+                    wallpaper: index as u8 % WALLPAPERS,
+                    pokemon: vec![None; BOX_SIZE],
+                    trainers: Default::default(),
+                };
+                b.pokemon[0] = Some(pokemon);
+                b.trainers.set(0, trainer);
+                self.boxes.push(b);
+            }
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.boxes
+            .iter()
+            .all(|b| b.pokemon.iter().all(Option::is_some))
+    }
+}
+
+impl PokemonBox {
+    /// Number of pokemon in the box.
+    pub fn count(&self) -> usize {
+        self.pokemon.iter().flatten().count()
+    }
+}
+
+impl OriginalTrainers {
+    pub fn get(&self, slot: usize) -> Option<u16> {
+        self.0.get(slot).copied().flatten()
+    }
+
+    pub fn set(&mut self, slot: usize, trainer: Option<u16>) {
+        self.fill(slot + 1);
+        self.0[slot] = trainer;
+    }
+
+    pub fn take(&mut self, slot: usize) -> Option<u16> {
+        self.0.get_mut(slot).and_then(Option::take)
+    }
+
+    /// Shifts later slots along, as when a pokemon is inserted into a party.
+    pub fn insert(&mut self, slot: usize, trainer: Option<u16>) {
+        self.fill(slot);
+        self.0.insert(slot, trainer);
+    }
+
+    /// Shifts later slots back, as when a pokemon leaves a party.
+    pub fn remove(&mut self, slot: usize) -> Option<u16> {
+        match slot < self.0.len() {
+            true => self.0.remove(slot),
+            false => None,
+        }
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.fill(a.max(b) + 1);
+        self.0.swap(a, b);
+    }
+
+    fn fill(&mut self, len: usize) {
+        if self.0.len() < len {
+            self.0.resize(len, None);
+        }
+    }
+}
+
+impl Default for PokemonStorage {
+    fn default() -> Self {
+        Self {
+            boxes: (0..BOXES)
+                .map(|index| PokemonBox {
+                    name: format!("BOX {}", index + 1),
+                    wallpaper: index as u8 % WALLPAPERS,
+                    pokemon: vec![None; BOX_SIZE],
+                    trainers: Default::default(),
+                })
+                .collect(),
+            current: 0,
+        }
+    }
+}
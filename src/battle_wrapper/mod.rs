@@ -8,7 +8,10 @@ use battlecli::battle::{
         PlayerSettings,
     },
 };
-use worldcli::worldlib::{character::player::PlayerCharacter, map::battle::BattleEntry};
+use worldcli::worldlib::{
+    character::player::PlayerCharacter,
+    map::{battle::BattleEntry, Weather},
+};
 
 use crate::{
     command::CommandProcessor,
@@ -17,14 +20,22 @@ use crate::{
         text::MessagePage,
         App, Plugins,
     },
-    pokedex::{item::Item, moves::Move, pokemon::Pokemon, trainer::InitTrainer, Dex},
+    pokedex::{
+        item::Item,
+        moves::Move,
+        pokemon::{owned::SavedPokemon, Level, Pokemon, PokemonId},
+        trainer::InitTrainer,
+        Dex,
+    },
     pokengine::texture::{ItemTextures, PokemonTextures, TrainerGroupTextures},
     random::GamePseudoRandom,
 };
 
 use worldcli::worldlib::map::battle::BattleId;
 
-use firecore_battle_engine::{BattlePlayerGui, BattleTrainer, InitBattleGuiTextures};
+use firecore_battle_engine::{
+    BattlePlayerGui, BattleTrainer, BattleWeather, InitBattleGuiTextures,
+};
 
 mod command;
 pub mod transition;
@@ -92,7 +103,12 @@ impl BattleWrapper {
         self.random.seed(seed);
     }
 
-    pub fn try_battle(&mut self, player: &PlayerCharacter, trainer: &InitTrainer) -> bool {
+    pub fn try_battle(
+        &mut self,
+        player: &PlayerCharacter,
+        trainer: &InitTrainer,
+        weather: Weather,
+    ) -> bool {
         let entry = match player.battle.battling.as_ref() {
             Some(entry) => entry,
             None => return false,
@@ -100,17 +116,24 @@ impl BattleWrapper {
 
         match trainer.party.iter().any(|pokemon| !(pokemon.fainted())) || entry.party.is_empty() {
             true => {
-                self.battle(&player.name, trainer, entry);
+                self.battle(&player.name, trainer, entry, weather);
                 true
             }
             false => false,
         }
     }
 
-    pub fn battle(&mut self, name: &str, trainer: &InitTrainer, entry: &BattleEntry) {
+    pub fn battle(
+        &mut self,
+        name: &str,
+        trainer: &InitTrainer,
+        entry: &BattleEntry,
+        weather: Weather,
+    ) {
         // add battle type parameter
         self.state = BattleManagerState::default();
         self.player.reset();
+        self.player.set_weather(Self::weather(weather));
         let player = PlayerData {
             id: BattleId::Player,
             name: Some(name.to_owned()),
@@ -139,7 +162,6 @@ impl BattleWrapper {
                 .map(|trainer| trainer.bag.clone())
                 .unwrap_or_default(),
             trainer: entry.trainer.as_ref().map(|t| BattleTrainer {
-                worth: t.worth,
                 texture: t.sprite,
                 defeat: t
                     .defeat
@@ -278,6 +300,42 @@ impl BattleWrapper {
         false
     }
 
+    /// The weather a battle starts with on a map with the given weather.
+    fn weather(weather: Weather) -> BattleWeather {
+        match weather {
+            Weather::Rain | Weather::Thunderstorm => BattleWeather::Rain,
+            Weather::Sunny => BattleWeather::Sun,
+            _ => BattleWeather::Clear,
+        }
+    }
+
+    /// Sets the highest level each party member obeys the player at.
+    pub fn set_obedience(&mut self, obedience: Vec<Option<Level>>) {
+        self.player.set_obedience(obedience);
+    }
+
+    /// Takes the pokemon the player caught in the last battle.
+    pub fn caught(&mut self) -> Vec<SavedPokemon> {
+        self.player.take_caught()
+    }
+
+    /// Copies the party's levels, experience and health after a battle back to the trainer.
+    pub fn sync(&self, trainer: &mut InitTrainer) {
+        for (pokemon, battled) in trainer.party.iter_mut().zip(self.player.party()) {
+            *pokemon = battled.clone();
+        }
+    }
+
+    /// Party members that were sent out in the last battle.
+    pub fn participants(&self) -> &[usize] {
+        self.player.participants()
+    }
+
+    /// Takes the opponent species the player saw in the last battle.
+    pub fn seen(&mut self) -> Vec<PokemonId> {
+        self.player.take_seen()
+    }
+
     pub fn winner(&self) -> Option<&BattleId> {
         self.battle.as_ref().and_then(|b| b.winner())
     }
@@ -330,3 +388,39 @@ impl BattleWrapper {
         gfx.render(draw);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rain_starts_battles_with_rain() {
+        assert_eq!(BattleWrapper::weather(Weather::Rain), BattleWeather::Rain);
+        assert_eq!(
+            BattleWrapper::weather(Weather::Thunderstorm),
+            BattleWeather::Rain
+        );
+        assert_eq!(BattleWrapper::weather(Weather::Sunny), BattleWeather::Sun);
+        assert_eq!(BattleWrapper::weather(Weather::Fog), BattleWeather::Clear);
+    }
+
+    #[test]
+    fn traded_pokemon_above_cap_disobey() {
+        let mut random = GamePseudoRandom::default();
+        let mut player = PlayerCharacter {
+            id: Some(1),
+            ..Default::default()
+        };
+        player.original_trainers.set(0, player.id);
+        player.trade(1, &mut random);
+
+        let obedience = player.obedience(2, 10);
+        assert_eq!(obedience, [None, Some(10)]);
+
+        let disobeys = |random: &mut GamePseudoRandom, level| {
+            (0..16).any(|_| BattlePlayerGui::<BattleId>::disobeys(random, level, 10))
+        };
+        assert!(disobeys(&mut random, 50));
+        assert!(!disobeys(&mut random, 10));
+    }
+}
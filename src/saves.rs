@@ -1,27 +1,47 @@
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::{hash::Hash, sync::Arc};
+use std::{fmt::Display, hash::Hash, path::Path, sync::Arc};
+
+use firecore_storage::{error::DataError, DataSerializer, RonSerializer};
 
 use crate::engine::HashMap;
 
-use crate::worldcli::worldlib::{script::default::DefaultWorldScriptEngine, state::WorldState};
+use crate::worldcli::worldlib::{
+    map::data::WorldMaps, positions::Location, script::default::DefaultWorldScriptEngine,
+    state::WorldState,
+};
 
 use crate::pokedex::{
     item::Item,
     moves::Move,
-    pokemon::Pokemon,
+    pokemon::{owned::SavedPokemon, Pokemon},
     trainer::{InitTrainer, SavedTrainer},
     Dex,
 };
 
+use crate::random::GamePseudoRandom;
+
 #[derive(Debug)]
 pub struct SaveManager<ID: Eq + Hash + Clone> {
     pub current: Option<(ID, InitPlayer)>,
     pub saves: HashMap<ID, SavedPlayer>,
+    /// Slots that could not be loaded
+    pub errors: HashMap<ID, SaveError>,
     pub pokedex: Arc<Dex<Pokemon>>,
     pub movedex: Arc<Dex<Move>>,
     pub itemdex: Arc<Dex<Item>>,
+    random: GamePseudoRandom,
 }
 
+/// Number of save slots shown in the load menu.
+pub const SLOTS: u8 = 3;
+
+/// Version of the save schema written by this build.
+/// Bump it and add a step to [migration] whenever saved data changes.
+pub const SAVE_VERSION: SaveVersion = SaveVersion(1);
+
+mod migration;
+
 // mod list;
 
 // pub use list::PlayerSaves;
@@ -33,8 +53,8 @@ pub type InitPlayer = Player<InitTrainer>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player<T> {
-    #[serde(default = "default_version")]
-    pub version: String,
+    #[serde(default)]
+    pub version: SaveVersion,
 
     #[serde(default)]
     pub world: GameWorldState,
@@ -52,12 +72,18 @@ impl<ID: Eq + Hash + Clone> SaveManager<ID> {
         Self {
             current: Default::default(),
             saves: Default::default(),
+            errors: Default::default(),
             pokedex,
             movedex,
             itemdex,
+            random: Default::default(),
         }
     }
 
+    pub fn seed(&mut self, seed: u64) {
+        self.random.seed(seed);
+    }
+
     pub fn create(&mut self, id: ID, name: impl Into<String>, rival: impl Into<String>) {
         self.saves.insert(id, Player::new(name, rival));
     }
@@ -68,8 +94,14 @@ impl<ID: Eq + Hash + Clone> SaveManager<ID> {
             self.saves.insert(id, player.uninit());
         }
         let new = self.saves.remove(&id);
-        if let Some(new) = new.and_then(|new| new.init(&self.pokedex, &self.movedex, &self.itemdex))
-        {
+        if let Some(new) = new.and_then(|new| {
+            new.init(
+                &mut self.random,
+                &self.pokedex,
+                &self.movedex,
+                &self.itemdex,
+            )
+        }) {
             self.current = Some((id, new));
         }
         self.current.is_some()
@@ -88,14 +120,198 @@ impl<ID: Eq + Hash + Clone> SaveManager<ID> {
             self.saves
                 .insert(current.0.clone(), current.1.clone().uninit());
         }
-        worldcli::engine::log::info!("to - do: save to file");
+    }
+}
+
+impl<ID: Eq + Hash + Clone + Display> SaveManager<ID> {
+    /// Reads every given slot that has a save on disk.
+    pub fn load(&mut self, ids: impl IntoIterator<Item = ID>) {
+        for id in ids {
+            match Self::read(&id) {
+                Ok(Some(save)) => {
+                    self.saves.insert(id, save);
+                }
+                Ok(None) => (),
+                Err(err) => {
+                    worldcli::engine::log::error!("Could not load save {} with error {}", id, err);
+                    self.errors.insert(id, err);
+                }
+            }
+        }
+    }
+
+    fn read(id: &ID) -> Result<Option<SavedPlayer>, SaveError> {
+        let file = Self::file(id);
+        let data = match firecore_storage::load_raw_as::<RonSerializer>(
+            &file,
+            Some(crate::PUBLISHER),
+            crate::APPLICATION,
+        )? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let version = SaveHeader::read(&data)?;
+        if version > SAVE_VERSION {
+            let backup = format!("{}_v{}", file, version.0);
+            firecore_storage::copy_as::<RonSerializer>(
+                &file,
+                &backup,
+                Some(crate::PUBLISHER),
+                crate::APPLICATION,
+            )?;
+            return Err(SaveError::Newer(version, backup));
+        }
+        let mut save = migration::upgrade(version, &data)?;
+        save.world.map.player.pc.repair();
+        Ok(Some(save))
+    }
+
+    /// Gets the world state of a slot, whether it is loaded or not.
+    pub fn world(&self, id: &ID) -> Option<&GameWorldState> {
+        match self.current.as_ref().filter(|(c, ..)| c == id) {
+            Some((.., player)) => Some(&player.world),
+            None => self.saves.get(id).map(|save| &save.world),
+        }
+    }
+
+    /// Writes the current player to its slot.
+    pub fn write(&mut self) {
+        self.save();
+        if let Some((id, ..)) = self.current.as_ref() {
+            if let Some(save) = self.saves.get(id) {
+                Self::store(id, save);
+            }
+        }
+    }
+
+    pub fn create_slot(&mut self, id: ID, name: impl Into<String>, rival: impl Into<String>) {
+        self.errors.remove(&id);
+        self.create(id.clone(), name, rival);
+        if let Some(save) = self.saves.get(&id) {
+            Self::store(&id, save);
+        }
+    }
+
+    pub fn delete(&mut self, id: &ID) {
+        if matches!(&self.current, Some((current, ..)) if current == id) {
+            self.current = None;
+        }
+        self.saves.remove(id);
+        self.errors.remove(id);
+        if let Err(err) = firecore_storage::remove_as::<RonSerializer>(
+            &Self::file(id),
+            Some(crate::PUBLISHER),
+            crate::APPLICATION,
+        ) {
+            worldcli::engine::log::error!("Could not delete save {} with error {}", id, err);
+        }
+    }
+
+    pub fn copy(&mut self, from: &ID, to: ID) {
+        self.save();
+        if let Some(save) = self.saves.get(from).cloned() {
+            Self::store(&to, &save);
+            self.saves.insert(to, save);
+        }
+    }
+
+    /// Writes the current player to a ron file that can be edited by hand.
+    pub fn export(&mut self, path: &Path) -> Result<(), SaveError> {
+        self.save();
+        let save = self
+            .current
+            .as_ref()
+            .and_then(|(id, ..)| self.saves.get(id))
+            .ok_or_else(|| SaveError::Invalid("No save is loaded".to_owned()))?;
+        write_file(path, RonSerializer::serialize(save)?)
+    }
+
+    /// Replaces the current player with an exported save after checking it against the loaded data.
+    pub fn import(&mut self, path: &Path, maps: &WorldMaps) -> Result<(), SaveError> {
+        let id = match self.current.as_ref() {
+            Some((id, ..)) => id.clone(),
+            None => return Err(SaveError::Invalid("No save is loaded".to_owned())),
+        };
+        let data = read_file(path)?;
+        let version = SaveHeader::read(&data)?;
+        if version > SAVE_VERSION {
+            return Err(SaveError::Invalid(format!(
+                "Save version {} is newer than supported version {}",
+                version.0, SAVE_VERSION.0
+            )));
+        }
+        let mut save = migration::upgrade(version, &data)?;
+        save.world.map.player.pc.repair();
+        save.validate(&self.pokedex, &self.movedex, &self.itemdex, maps)?;
+        let player = save
+            .init(
+                &mut self.random,
+                &self.pokedex,
+                &self.movedex,
+                &self.itemdex,
+            )
+            .ok_or_else(|| {
+                SaveError::Invalid("Save contains an unknown pokemon, move or item".to_owned())
+            })?;
+        self.current = Some((id, player));
+        Ok(())
+    }
+
+    fn store(id: &ID, save: &SavedPlayer) {
+        if let Err(err) = firecore_storage::save_as::<RonSerializer, SavedPlayer>(
+            save,
+            &Self::file(id),
+            Some(crate::PUBLISHER),
+            crate::APPLICATION,
+        ) {
+            worldcli::engine::log::error!("Could not write save {} with error {}", id, err);
+        }
+    }
+
+    fn file(id: &ID) -> String {
+        format!("save_{}", id)
+    }
+}
+
+/// Exported saves are plain files, which the browser can't reach.
+#[cfg(target_arch = "wasm32")]
+fn unsupported() -> SaveError {
+    SaveError::Storage(DataError::IOError(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Save files can't be exported or imported in the browser",
+    )))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, SaveError> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = path;
+        Err(unsupported())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read(path).map_err(|err| DataError::from(err).into())
+    }
+}
+
+fn write_file(path: &Path, data: Vec<u8>) -> Result<(), SaveError> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (path, data);
+        Err(unsupported())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::write(path, data).map_err(|err| DataError::from(err).into())
     }
 }
 
 impl<T: Default> Player<T> {
     pub fn new(name: impl Into<String>, rival: impl Into<String>) -> Self {
         Self {
-            version: default_version(),
+            version: SAVE_VERSION,
             world: GameWorldState::new(name, rival),
             trainer: Default::default(),
         }
@@ -105,20 +321,170 @@ impl<T: Default> Player<T> {
 impl<T: Default> Default for Player<T> {
     fn default() -> Self {
         Self {
-            version: default_version(),
+            version: SAVE_VERSION,
             world: Default::default(),
             trainer: Default::default(),
         }
     }
 }
 
-fn default_version() -> String {
-    crate::VERSION.to_owned()
+/// Saves without a version, or with the old crate version string, are version 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "RawVersion", into = "u16")]
+pub struct SaveVersion(pub u16);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawVersion {
+    Schema(u16),
+    Legacy(String),
+}
+
+impl From<RawVersion> for SaveVersion {
+    fn from(raw: RawVersion) -> Self {
+        match raw {
+            RawVersion::Schema(version) => Self(version),
+            RawVersion::Legacy(..) => Self(0),
+        }
+    }
+}
+
+impl From<SaveVersion> for u16 {
+    fn from(version: SaveVersion) -> Self {
+        version.0
+    }
+}
+
+/// Reads only the version of a save, so newer saves are not deserialized.
+#[derive(Deserialize)]
+struct SaveHeader {
+    #[serde(default)]
+    version: SaveVersion,
+}
+
+impl SaveHeader {
+    fn read(data: &[u8]) -> Result<SaveVersion, DataError> {
+        RonSerializer::deserialize::<Self>(data).map(|header| header.version)
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Storage(DataError),
+    /// The save was written by a newer build. It has been copied to the backup file.
+    Newer(SaveVersion, String),
+    /// The save refers to data that does not exist in this build.
+    Invalid(String),
+}
+
+impl std::error::Error for SaveError {}
+
+impl core::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Storage(err) => core::fmt::Display::fmt(err, f),
+            Self::Newer(version, backup) => write!(
+                f,
+                "Save version {} is newer than supported version {}, backed up to {}",
+                version.0, SAVE_VERSION.0, backup
+            ),
+            Self::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl From<DataError> for SaveError {
+    fn from(error: DataError) -> Self {
+        Self::Storage(error)
+    }
 }
 
 impl SavedPlayer {
+    /// Checks that every pokemon, move, item and location in the save exists.
+    pub fn validate(
+        &self,
+        pokedex: &Dex<Pokemon>,
+        movedex: &Dex<Move>,
+        itemdex: &Dex<Item>,
+        maps: &WorldMaps,
+    ) -> Result<(), SaveError> {
+        for pokemon in self.trainer.party.iter() {
+            Self::validate_pokemon(pokemon, "party", pokedex, movedex, itemdex)?;
+        }
+
+        for pokemon in self
+            .world
+            .map
+            .player
+            .pc
+            .boxes
+            .iter()
+            .flat_map(|b| b.pokemon.iter().flatten())
+        {
+            Self::validate_pokemon(pokemon, "PC", pokedex, movedex, itemdex)?;
+        }
+
+        if let Some(stack) = self
+            .trainer
+            .bag
+            .iter()
+            .find(|stack| itemdex.try_get(&stack.item).is_none())
+        {
+            return Err(SaveError::Invalid(format!(
+                "Unknown item {} in bag",
+                stack.item
+            )));
+        }
+
+        let map = &self.world.map;
+        let mut locations = std::iter::once(&map.location)
+            .filter(|location| **location != Location::DEFAULT)
+            .chain(map.entities.keys())
+            .chain(map.places.heal.iter().map(|spot| &spot.location))
+            .chain(map.places.cave.iter().map(|spot| &spot.location))
+            .chain(map.places.visited.iter())
+            .chain(map.warp.iter().map(|warp| &warp.location));
+        match locations.find(|location| !maps.contains_key(location)) {
+            Some(location) => Err(SaveError::Invalid(format!("Unknown location {}", location))),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_pokemon(
+        pokemon: &SavedPokemon,
+        place: &str,
+        pokedex: &Dex<Pokemon>,
+        movedex: &Dex<Move>,
+        itemdex: &Dex<Item>,
+    ) -> Result<(), SaveError> {
+        if pokedex.try_get(&pokemon.pokemon).is_none() {
+            return Err(SaveError::Invalid(format!(
+                "Unknown pokemon {} in {}",
+                pokemon.pokemon, place
+            )));
+        }
+        if let Some(m) = pokemon
+            .moves
+            .iter()
+            .find(|m| movedex.try_get(&m.0).is_none())
+        {
+            return Err(SaveError::Invalid(format!(
+                "Unknown move {} on pokemon {} in {}",
+                m.0, pokemon.pokemon, place
+            )));
+        }
+        match pokemon.item.filter(|item| itemdex.try_get(item).is_none()) {
+            Some(item) => Err(SaveError::Invalid(format!(
+                "Unknown item {} held by pokemon {} in {}",
+                item, pokemon.pokemon, place
+            ))),
+            None => Ok(()),
+        }
+    }
+
     pub fn init(
         self,
+        random: &mut impl Rng,
         pokedex: &Dex<Pokemon>,
         movedex: &Dex<Move>,
         itemdex: &Dex<Item>,
@@ -126,9 +492,7 @@ impl SavedPlayer {
         Some(InitPlayer {
             version: self.version,
             world: self.world,
-            trainer: self
-                .trainer
-                .init(&mut rand::thread_rng(), pokedex, movedex, itemdex)?,
+            trainer: self.trainer.init(random, pokedex, movedex, itemdex)?,
         })
     }
 }
@@ -0,0 +1,69 @@
+//! Upgrades saves written by older builds to the current version.
+
+use firecore_storage::{error::DataError, DataSerializer, RonSerializer};
+
+use super::{SaveVersion, SavedPlayer, SAVE_VERSION};
+
+/// Deserializes a save written at the given version and upgrades it to [SAVE_VERSION].
+pub fn upgrade(version: SaveVersion, data: &[u8]) -> Result<SavedPlayer, DataError> {
+    let mut save: SavedPlayer = RonSerializer::deserialize(data)?;
+    if version.0 == 0 {
+        legacy(&mut save);
+    }
+    save.version = SAVE_VERSION;
+    Ok(save)
+}
+
+/// 0 -> 1: the crate version string was replaced by the schema version.
+/// Saves from before then have no pokedex, so the party counts as caught.
+fn legacy(save: &mut SavedPlayer) {
+    let dex = &mut save.world.map.player.dex;
+    for pokemon in save.trainer.party.iter() {
+        dex.catch(pokemon.pokemon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::worldcli::worldlib::{
+        character::npc::trainer::BadgeId,
+        state::{map::Whiteout, storage::PokemonStorage},
+    };
+
+    fn load(data: &[u8]) -> SavedPlayer {
+        let version = crate::saves::SaveHeader::read(data).unwrap();
+        upgrade(version, data).unwrap()
+    }
+
+    fn boulder() -> BadgeId {
+        "boulder".parse().unwrap()
+    }
+
+    #[test]
+    fn legacy_save() {
+        let save = load(include_bytes!("fixtures/v0.ron"));
+        let player = &save.world.map.player;
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(player.name, "Red");
+        assert_eq!(player.rival, "Blue");
+        assert!(player.badges.contains(&boulder()));
+        assert!(player.badge_dates.is_empty());
+        assert_eq!(player.pc.boxes.len(), PokemonStorage::default().boxes.len());
+        assert!(player.dex.caught.is_empty());
+        assert_eq!(player.id, None);
+        assert_eq!(save.world.map.whiteout, None);
+    }
+
+    #[test]
+    fn current_save() {
+        let save = load(include_bytes!("fixtures/v1.ron"));
+        let player = &save.world.map.player;
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(player.id, Some(12345));
+        assert_eq!(player.play_time, 3600.0);
+        assert_eq!(player.badge_dates.get(&boulder()), Some(&1600000000));
+        assert_eq!(save.world.map.whiteout, Some(Whiteout::Healing));
+    }
+}
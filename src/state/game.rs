@@ -1,13 +1,20 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc, sync::Arc};
 
 use crate::{
     command::CommandProcessor,
     engine::{
         graphics::{Font, Graphics},
+        log::{error, info, warn},
         music::stop_music,
         App, Plugins,
     },
-    pokedex::{item::Item, moves::Move, pokemon::Pokemon, Dex},
+    pokedex::{
+        item::Item,
+        moves::Move,
+        pokemon::{Level, Pokemon, PokemonId},
+        trainer::InitTrainer,
+        Dex,
+    },
     random::GamePseudoRandom,
     saves::{Player, SaveManager},
     world_wrapper::WorldRequest, settings::Settings,
@@ -15,12 +22,22 @@ use crate::{
 
 use firecore_battle_engine::{
     battle::default_engine::DefaultBattleEngine,
-    pokengine::texture::{ItemTextures, PokemonTextures, TrainerGroupTextures},
+    pokengine::{
+        gui::evolution::EvolutionGui,
+        texture::{ItemTextures, PokemonTextures, TrainerGroupTextures},
+    },
     BattleGuiTextures,
 };
 use worldcli::{
     map::data::ClientWorldData,
-    worldlib::map::{battle::BattleId, manager::WorldMapManager},
+    worldlib::{
+        map::{
+            battle::BattleId,
+            evolution::{evolution, EvolutionTrigger},
+            manager::WorldMapManager,
+        },
+        state::map::{MapEvent, MapState},
+    },
 };
 
 use crate::battle_wrapper::BattleWrapper;
@@ -29,6 +46,7 @@ use crate::world_wrapper::WorldWrapper;
 pub enum GameStates {
     World,
     Battle,
+    Evolution,
 }
 
 impl Default for GameStates {
@@ -43,20 +61,29 @@ pub(super) struct GameStateManager {
     pub world: WorldWrapper,
     pub battle: BattleWrapper,
 
-    pub saves: Rc<RefCell<SaveManager<String>>>,
+    evolution: EvolutionGui,
+    /// Party members waiting to evolve, and what they evolve into
+    evolutions: VecDeque<(usize, PokemonId)>,
+    /// Party levels from before the last battle
+    levels: Vec<Level>,
+    random: GamePseudoRandom,
+
+    pub saves: Rc<RefCell<SaveManager<u8>>>,
 }
 
 impl GameStateManager {
     pub fn seed(&mut self, seed: u64) {
         self.world.seed(seed);
         self.battle.seed(seed);
+        self.random.seed(seed);
+        self.saves.borrow_mut().seed(seed);
     }
 }
 
 impl GameStateManager {
     pub fn load(
         gfx: &mut Graphics,
-        saves: Rc<RefCell<SaveManager<String>>>,
+        saves: Rc<RefCell<SaveManager<u8>>>,
         settings: Rc<Settings>,
         pokedex: Arc<Dex<Pokemon>>,
         movedex: Arc<Dex<Move>>,
@@ -77,6 +104,10 @@ impl GameStateManager {
         Ok(Self {
             state: Default::default(),
             saves,
+            evolution: EvolutionGui::new(pokemon.clone()),
+            evolutions: Default::default(),
+            levels: Default::default(),
+            random: Default::default(),
             world: WorldWrapper::new(
                 WorldMapManager {
                     data: firecore_storage::from_bytes(include_bytes!(concat!(
@@ -145,7 +176,7 @@ impl GameStateManager {
                     self.world.start(&mut save.world, &mut save.trainer);
                 }
             }
-            GameStates::Battle => (),
+            GameStates::Battle | GameStates::Evolution => (),
         }
     }
 
@@ -154,34 +185,204 @@ impl GameStateManager {
     }
 
     pub fn update(&mut self, app: &mut App, plugins: &mut Plugins, delta: f32) {
-        if let Some(player) = self.saves.borrow_mut().current_mut() {
+        let mut request = None;
+        let saves = self.saves.clone();
+        if let Some(player) = saves.borrow_mut().current_mut() {
             let Player {
                 version,
                 world,
                 trainer,
             } = player;
             let state = world;
+            // counted in real time, ignoring the fast forward key
+            state.map.player.play_time += app.timer.delta_f32() as f64;
             match self.state {
                 GameStates::World => {
-                    self.world.update(app, plugins, state, trainer, delta);
-                    if self.battle.try_battle(&mut state.map.player, trainer) {
+                    request = self.world.update(app, plugins, state, trainer, delta);
+                    let weather = self
+                        .world
+                        .manager
+                        .get(&state.map.location)
+                        .map(|map| map.settings.weather)
+                        .unwrap_or_default();
+                    if self
+                        .battle
+                        .try_battle(&mut state.map.player, trainer, weather)
+                    {
+                        let cap = self.world.manager.world.data.obedience(&state.map.player);
+                        self.battle
+                            .set_obedience(state.map.player.obedience(trainer.party.len(), cap));
+                        self.levels = trainer.party.iter().map(|p| p.level).collect();
                         self.state = GameStates::Battle;
                     }
                 }
                 GameStates::Battle => {
                     if self.battle.update(app, plugins, &mut state.map.player) {
+                        self.battle.sync(trainer);
+                        let levels = std::mem::take(&mut self.levels);
+                        state.map.events.extend(Self::level_moves(trainer, &levels));
+                        // checked before a whiteout heals the party
+                        self.level_evolutions(trainer, &levels);
                         if let Some(winner) = self.battle.winner() {
                             let winner = winner == &BattleId::Player;
-                            self.world
-                                .manager
-                                .post_battle(&mut state.map, trainer, winner);
+                            self.world.manager.post_battle(
+                                &mut state.map,
+                                trainer,
+                                winner,
+                                self.battle.participants(),
+                            );
+                        }
+                        state.map.player.dex.seen.extend(self.battle.seen());
+                        state
+                            .map
+                            .events
+                            .extend(self.battle.caught().into_iter().map(MapEvent::GivePokemon));
+                        if !self.next_evolution(trainer) {
+                            self.state = GameStates::World;
+                            self.world.start(state, trainer);
+                        }
+                    }
+                }
+                GameStates::Evolution => (),
+            }
+        }
+        if let Some(request) = request {
+            self.request(request);
+        }
+    }
+
+    /// Moves the party learned by leveling up in the last battle.
+    fn level_moves<'a>(
+        trainer: &'a InitTrainer,
+        levels: &'a [Level],
+    ) -> impl Iterator<Item = MapEvent> + 'a {
+        trainer
+            .party
+            .iter()
+            .zip(levels)
+            .enumerate()
+            .flat_map(|(index, (pokemon, previous))| {
+                pokemon
+                    .pokemon
+                    .moves
+                    .iter()
+                    .filter(move |m| m.0 > *previous && m.0 <= pokemon.level)
+                    .map(move |m| MapEvent::LearnMove(index, m.1))
+            })
+    }
+
+    /// Queues the party members that leveled up in the last battle, did not faint and can evolve.
+    fn level_evolutions(&mut self, trainer: &InitTrainer, levels: &[Level]) {
+        let evolutions = &self.world.manager.world.data.evolutions;
+        self.evolutions.extend(
+            trainer
+                .party
+                .iter()
+                .zip(levels)
+                .enumerate()
+                .filter(|(.., (pokemon, level))| pokemon.level > **level && !pokemon.fainted())
+                .filter_map(|(index, (pokemon, ..))| {
+                    evolution(
+                        evolutions,
+                        &pokemon.pokemon.id,
+                        EvolutionTrigger::Level(pokemon.level),
+                    )
+                    .map(|into| (index, into))
+                }),
+        );
+    }
+
+    /// Starts the next queued evolution. Returns false if there are none left.
+    fn next_evolution(&mut self, trainer: &InitTrainer) -> bool {
+        while let Some((index, into)) = self.evolutions.front().copied() {
+            match (
+                trainer.party.get(index),
+                self.world.manager.world.pokedex.try_get(&into),
+            ) {
+                (Some(pokemon), Some(evolved)) => {
+                    self.evolution.spawn(
+                        pokemon.name().to_string(),
+                        pokemon.pokemon.id,
+                        into,
+                        evolved.name.clone(),
+                    );
+                    self.state = GameStates::Evolution;
+                    return true;
+                }
+                _ => {
+                    self.evolutions.pop_front();
+                }
+            }
+        }
+        false
+    }
+
+    /// Changes the species of a party member, keeping its stats, moves and nickname.
+    /// Moves the new species learns at its current level are queued to be learned.
+    fn evolve(
+        &mut self,
+        trainer: &mut InitTrainer,
+        state: &mut MapState,
+        index: usize,
+        into: PokemonId,
+    ) {
+        let world = &self.world.manager.world;
+        if let Some(pokemon) = trainer.party.get_mut(index) {
+            let mut saved = pokemon.clone().uninit();
+            saved.pokemon = into;
+            match saved.init(
+                &mut self.random,
+                &world.pokedex,
+                &world.movedex,
+                &world.itemdex,
+            ) {
+                Some(evolved) => {
+                    state.events.extend(
+                        evolved
+                            .pokemon
+                            .moves
+                            .iter()
+                            .filter(|m| m.0 == evolved.level)
+                            .map(|m| MapEvent::LearnMove(index, m.1)),
+                    );
+                    *pokemon = evolved;
+                    state.player.dex.catch(into);
+                }
+                None => warn!("Could not evolve pokemon into #{}", into),
+            }
+        }
+    }
+
+    /// Handles a request from the world. Returns true if the game should exit.
+    fn request(&mut self, request: WorldRequest) -> bool {
+        let saves = self.saves.clone();
+        let mut saves = saves.borrow_mut();
+        match request {
+            WorldRequest::Save => saves.write(),
+            WorldRequest::Exit => return true,
+            WorldRequest::Export(path) => match saves.export(path.as_ref()) {
+                Ok(()) => info!("Exported save to {}", path),
+                Err(err) => error!("Could not export save to {} with error {}", path, err),
+            },
+            WorldRequest::Import(path) => {
+                match saves.import(path.as_ref(), &self.world.manager.world.data.maps) {
+                    Ok(()) => {
+                        info!("Imported save from {}", path);
+                        if let Some(player) = saves.current_mut() {
+                            self.world.start(&mut player.world, &mut player.trainer);
                         }
-                        self.state = GameStates::World;
-                        self.world.start(state, trainer);
                     }
+                    Err(err) => error!("Could not import save from {} with error {}", path, err),
+                }
+            }
+            WorldRequest::Evolve(index, into) => {
+                if let Some(player) = saves.current() {
+                    self.evolutions.push_back((index, into));
+                    self.next_evolution(&player.trainer);
                 }
             }
         }
+        false
     }
 
     pub fn draw(&self, gfx: &mut Graphics) {
@@ -199,6 +400,7 @@ impl GameStateManager {
                 }
                 self.battle.draw(gfx);
             }
+            GameStates::Evolution => (),
         }
     }
 
@@ -210,31 +412,45 @@ impl GameStateManager {
     ) -> bool {
         match self.state {
             GameStates::World => {
-                if let Some(player) = self.saves.borrow_mut().current_mut() {
-                    if let Some(request) = self.world.ui(
+                let request = match self.saves.borrow_mut().current_mut() {
+                    Some(player) => self.world.ui(
                         app,
                         plugins,
                         egui,
                         &mut player.world.map,
                         &mut player.trainer,
-                    ) {
-                        match request {
-                            WorldRequest::Save => {
-                                self.saves.borrow_mut().save();
-                                false
-                            }
-                            WorldRequest::Exit => {
-                                return true;
+                    ),
+                    None => return true,
+                };
+                request
+                    .map(|request| self.request(request))
+                    .unwrap_or_default()
+            }
+            GameStates::Battle => self.battle.ui(app, plugins, egui),
+            GameStates::Evolution => {
+                let delta = app.timer.delta_f32();
+                if let Some(evolved) = self.evolution.ui(app, plugins, egui, delta) {
+                    let saves = self.saves.clone();
+                    let mut saves = saves.borrow_mut();
+                    if let Some(player) = saves.current_mut() {
+                        if let Some((index, into)) = self.evolutions.pop_front() {
+                            if evolved {
+                                self.evolve(
+                                    &mut player.trainer,
+                                    &mut player.world.map,
+                                    index,
+                                    into,
+                                );
                             }
                         }
-                    } else {
-                        false
+                        if !self.next_evolution(&player.trainer) {
+                            self.state = GameStates::World;
+                            self.world.start(&mut player.world, &mut player.trainer);
+                        }
                     }
-                } else {
-                    true
                 }
+                false
             }
-            GameStates::Battle => self.battle.ui(app, plugins, egui),
         }
     }
 }
@@ -14,13 +14,14 @@ use crate::{
         graphics::{Color, CreateDraw, Graphics},
         App, Plugins,
     },
-    saves::SaveManager,
+    saves::{SaveManager, SLOTS},
     state::MainStates, settings::Settings,
 };
 
 use super::{
     console::Console, game::GameStateManager, loading::LoadingStateManager,
-    menu::title::TitleState, StateManager,
+    menu::{saves::SaveMenu, title::TitleState},
+    StateManager,
 };
 
 use crate::engine::notan;
@@ -32,9 +33,10 @@ pub struct Game {
     loading: LoadingStateManager,
 
     title: TitleState,
+    save_menu: SaveMenu,
     game: GameStateManager,
 
-    saves: Rc<RefCell<SaveManager<String>>>,
+    saves: Rc<RefCell<SaveManager<u8>>>,
     
     settings: Rc<Settings>,
 
@@ -121,6 +123,8 @@ impl Game {
             itemdex.clone(),
         )));
 
+        saves.borrow_mut().load(0..SLOTS);
+
         for (id, music) in
             postcard::from_bytes::<HashMap<MusicId, Vec<u8>>>(include_bytes!(concat!(env!("OUT_DIR"), "/music.bin")))
                 .map_err(|err| format!("Cannot decode music binary with error {err}"))?
@@ -145,6 +149,7 @@ impl Game {
                 )))
                 .map_err(|err| err.to_string())?,
             )?,
+            save_menu: Default::default(),
             game: GameStateManager::load(
                 gfx,
                 saves.clone(),
@@ -283,22 +288,14 @@ impl Game {
                     self.settings.ui(app, plugins, egui);
 
                     egui::Window::new("Menu").show(egui, |ui| {
-                        if ui.button("Play").clicked() {
-                            if self.saves.borrow().current.is_some() {
-                                self.state.queue(MainStates::Game);
-                            }
-                        }
-                        if ui.button("New Save / Reset Save").clicked() {
-                            let mut saves = self.saves.borrow_mut();
-                            let id = format!(
-                                "{:?}",
-                                std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                            );
-                            saves.create(id.clone(), "Red", "Blue");
-                            saves.set_current(id);
+                        if self.save_menu.ui(
+                            ui,
+                            &mut self.saves.borrow_mut(),
+                            &self.game.world.manager,
+                        ) {
+                            self.state.queue(MainStates::Game);
                         }
+                        ui.separator();
                         if ui.button("Settings").clicked() {
                             self.settings.spawn();
                         }
@@ -1 +1,2 @@
+pub mod saves;
 pub mod title;
@@ -0,0 +1,103 @@
+use worldcli::{
+    gui::TrainerCardGui, map::manager::WorldManager,
+    worldlib::script::default::DefaultWorldScriptEngine,
+};
+
+use crate::{
+    engine::egui,
+    saves::{SaveManager, SLOTS},
+};
+
+/// Continue / load menu listing every save slot.
+#[derive(Debug, Default)]
+pub struct SaveMenu {
+    /// Slot waiting for the player to confirm its deletion
+    deleting: Option<u8>,
+}
+
+impl SaveMenu {
+    /// Returns true when a save has been loaded and the game should start.
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        saves: &mut SaveManager<u8>,
+        world: &WorldManager<DefaultWorldScriptEngine>,
+    ) -> bool {
+        let mut play = false;
+
+        if let Some(current) = saves.current() {
+            TrainerCardGui::card(
+                ui,
+                &current.world.map.player,
+                current.trainer.money,
+                &world.world.data.badges,
+            );
+            if ui.button("Continue").clicked() {
+                play = true;
+            }
+        }
+
+        for slot in 0..SLOTS {
+            ui.separator();
+            match saves.world(&slot) {
+                Some(state) => {
+                    let location = world
+                        .get(&state.map.location)
+                        .map(|map| map.name.as_str())
+                        .unwrap_or("Unknown");
+                    ui.label(format!(
+                        "Slot {}: {} - {} - Badges: {}",
+                        slot + 1,
+                        state.map.player.name,
+                        location,
+                        state.map.player.badges.len()
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button("Load").clicked() {
+                            play = saves.set_current(slot);
+                        }
+                        let empty = (0..SLOTS).find(|slot| {
+                            saves.world(slot).is_none() && !saves.errors.contains_key(slot)
+                        });
+                        if ui
+                            .add_enabled(empty.is_some(), egui::Button::new("Copy"))
+                            .clicked()
+                        {
+                            if let Some(empty) = empty {
+                                saves.copy(&slot, empty);
+                            }
+                        }
+                        match self.deleting == Some(slot) {
+                            true => {
+                                if ui.button("Confirm Delete").clicked() {
+                                    saves.delete(&slot);
+                                    self.deleting = None;
+                                }
+                                if ui.button("Cancel").clicked() {
+                                    self.deleting = None;
+                                }
+                            }
+                            false => {
+                                if ui.button("Delete").clicked() {
+                                    self.deleting = Some(slot);
+                                }
+                            }
+                        }
+                    });
+                }
+                None => {
+                    match saves.errors.get(&slot) {
+                        Some(err) => ui.label(format!("Slot {}: {}", slot + 1, err)),
+                        None => ui.label(format!("Slot {}: Empty", slot + 1)),
+                    };
+                    if ui.button("New Game").clicked() {
+                        saves.create_slot(slot, "Red", "Blue");
+                        play = saves.set_current(slot);
+                    }
+                }
+            }
+        }
+
+        play
+    }
+}
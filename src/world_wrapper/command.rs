@@ -7,7 +7,10 @@ use crate::{
 
 use worldcli::{
     pokedex::moves::MoveId,
-    worldlib::{positions::{Location, LocationId}, character::Capability},
+    worldlib::{
+        character::{npc::trainer::BadgeId, Capability},
+        positions::{Location, LocationId},
+    },
 };
 
 use super::WorldWrapper;
@@ -18,10 +21,14 @@ pub enum WorldCommands {
     GivePokemon(SavedPokemon),
     GiveMove(MoveId, usize),
     GiveItem(SavedItemStack),
+    GiveBadge(BadgeId),
     ToggleCapability(Capability),
     HealPokemon(Option<usize>),
     Warp(Location),
     Wild(Option<bool>),
+    EncounterRate(f32),
+    Time(u8),
+    TimeSpeed(f32),
     NoClip(Option<bool>),
     DebugDraw,
     Unfreeze,
@@ -30,10 +37,17 @@ pub enum WorldCommands {
     Party(PartyCommand),
     ClearBattle,
     Capabilities,
+    Export(String),
+    Import(String),
 }
 
 pub enum PartyCommand {
     Info(Option<usize>),
+    /// Trades the party member to another trainer and back,
+    /// giving it their trainer ID and evolving it if it evolves by trade.
+    /// Debug only: NPC trades are not scripted yet, so this is the only way to trigger
+    /// trade evolutions in game.
+    Trade(usize),
 }
 
 // pub enum BattleCommand {
@@ -77,6 +91,18 @@ impl WorldWrapper {
             //         // }
             "heal" => Ok(WorldCommands::HealPokemon(None)),
             "wild" => on_off(WorldCommands::Wild, args.next()),
+            "encounterrate" => match args.next().and_then(|arg| arg.parse::<f32>().ok()) {
+                Some(rate) => Ok(WorldCommands::EncounterRate(rate)),
+                None => Err("Please provide an encounter rate multiplier"),
+            },
+            "time" => match args.next().and_then(|arg| arg.parse::<u8>().ok()) {
+                Some(hour) => Ok(WorldCommands::Time(hour)),
+                None => Err("Please provide an hour"),
+            },
+            "timespeed" => match args.next().and_then(|arg| arg.parse::<f32>().ok()) {
+                Some(speed) => Ok(WorldCommands::TimeSpeed(speed)),
+                None => Err("Please provide how fast the clock should run"),
+            },
             "noclip" => on_off(WorldCommands::NoClip, args.next()),
             "unfreeze" => Ok(WorldCommands::Unfreeze),
             "cancelscript" => Ok(WorldCommands::CancelScript),
@@ -91,6 +117,10 @@ impl WorldWrapper {
                         },
                         None => Ok(WorldCommands::Party(PartyCommand::Info(None))),
                     },
+                    "trade" => match args.next().and_then(|arg| arg.parse::<usize>().ok()) {
+                        Some(index) => Ok(WorldCommands::Party(PartyCommand::Trade(index))),
+                        None => Err("Please provide a valid party index!"),
+                    },
                     _ => Err("Please provide a valid argument for /party"),
                 },
                 None => Err("Please provide an argument for /party"),
@@ -189,6 +219,14 @@ impl WorldWrapper {
                 }
             }
             "clearbattle" => Ok(WorldCommands::ClearBattle),
+            "export" => match args.next() {
+                Some(path) => Ok(WorldCommands::Export(path.to_owned())),
+                None => Err("Please provide a file to export the save to"),
+            },
+            "import" => match args.next() {
+                Some(path) => Ok(WorldCommands::Import(path.to_owned())),
+                None => Err("Please provide a file to import the save from"),
+            },
             "capabilities" => Ok(WorldCommands::Capabilities),
             "give" => match args.next() {
                 Some(arg) => match arg {
@@ -241,9 +279,13 @@ impl WorldWrapper {
                             Err("Invalid formatted capability ID")
                         }
                     }
+                    "badge" => match args.next().and_then(|arg| arg.parse::<BadgeId>().ok()) {
+                        Some(badge) => Ok(WorldCommands::GiveBadge(badge)),
+                        None => Err("Please provide a valid badge ID!"),
+                    },
                     _ => Err("Please provide an item ID"),
                 },
-                None => Err("Please provide an argument for /give: pokemon, item"),
+                None => Err("Please provide an argument for /give: pokemon, item, badge"),
             },
             _ => Err("Unknown command."),
         }
@@ -11,10 +11,16 @@ use crate::engine::{
 
 use firecore_battle_engine::pokengine::texture::{PokemonTextures, ItemTextures};
 use worldcli::{
+    gui::WorldMapGui,
     map::{data::ClientWorldData, manager::WorldManager},
-    pokedex::{moves::owned::OwnedMove, trainer::InitTrainer},
+    pokedex::{moves::owned::OwnedMove, pokemon::PokemonId, trainer::InitTrainer},
     worldlib::{
-        character::CharacterState, map::manager::WorldMapManager,
+        character::CharacterState,
+        map::{
+            data::FieldType,
+            evolution::{evolution, EvolutionTrigger},
+            manager::WorldMapManager,
+        },
         script::default::DefaultWorldScriptEngine,
         state::map::MapState,
     },
@@ -36,6 +42,12 @@ pub struct WorldWrapper {
 pub enum WorldRequest {
     Save,
     Exit,
+    /// Write the save to a ron file
+    Export(String),
+    /// Replace the save with a ron file
+    Import(String),
+    /// Evolve the party member at the index into a species (i.e. from an evolution stone)
+    Evolve(usize, PokemonId),
 }
 
 impl WorldWrapper {
@@ -47,15 +59,27 @@ impl WorldWrapper {
         items: Arc<ItemTextures>,
         commands: CommandProcessor,
     ) -> Self {
+        let field = world
+            .data
+            .moves
+            .iter()
+            .filter(|(.., t)| !matches!(t, FieldType::Capability(..)))
+            .map(|(id, ..)| *id)
+            .collect();
+        let areas = world.data.areas();
         Self {
             alive: false,
             manager: WorldManager {
-                world,
                 data,
                 warper: Default::default(),
                 input: Default::default(),
+                town_map: WorldMapGui::new(&world.data.maps),
+                pc: Default::default(),
+                learn: Default::default(),
+                weather: Default::default(),
+                world,
             },
-            menu: StartMenu::new(settings, pokemon, items),
+            menu: StartMenu::new(settings, pokemon, items, field, areas),
             commands,
             randoms: Default::default(),
             // events,
@@ -78,7 +102,9 @@ impl WorldWrapper {
         state: &mut GameWorldState,
         trainer: &mut InitTrainer,
         delta: f32,
-    ) {
+    ) -> Option<WorldRequest> {
+        let mut request = None;
+
         if pressed(app, plugins, Control::Start) && !state.map.player.character.input_lock.active()
         {
             self.menu.spawn();
@@ -126,6 +152,11 @@ impl WorldWrapper {
                                 .insert(CharacterState::ENCOUNTERS);
                         }
                     }
+                    WorldCommands::EncounterRate(rate) => {
+                        self.manager.world.data.encounters.multiplier = rate;
+                    }
+                    WorldCommands::Time(hour) => state.map.clock.set_hour(hour),
+                    WorldCommands::TimeSpeed(speed) => state.map.clock.speed = speed,
                     WorldCommands::NoClip(toggle) => {
                         if state
                             .map
@@ -166,7 +197,12 @@ impl WorldWrapper {
                             &self.manager.world.movedex,
                             &self.manager.world.itemdex,
                         ) {
-                            Some(pokemon) => trainer.party.push(pokemon),
+                            Some(pokemon) => {
+                                let index = trainer.party.len();
+                                trainer.party.push(pokemon);
+                                let player = &mut state.map.player;
+                                player.original_trainers.set(index, player.id);
+                            }
                             None => info!("Could not initialize pokemon!"),
                         }
                         // player.give_pokemon(pokemon);
@@ -183,12 +219,21 @@ impl WorldWrapper {
                         match stack.init(&self.manager.world.itemdex) {
                             Some(stack) => {
                                 trainer.bag.insert(stack);
-                                self.manager.update_capabilities(&mut state.map.player.character, trainer);
+                                self.manager.update_capabilities(&mut state.map.player, trainer);
                             },
                             None => info!("Could not initialize item!"),
                         }
                         // player.trainer.bag.insert_saved(stack);
                     }
+                    WorldCommands::GiveBadge(badge) => {
+                        match self.manager.world.data.badges.contains_key(&badge) {
+                            true => {
+                                state.map.player.earn_badge(badge);
+                                self.manager.update_capabilities(&mut state.map.player, trainer);
+                            }
+                            false => info!("Could not find badge {}", badge),
+                        }
+                    }
                     WorldCommands::Tile => match self.manager.get(&state.map.location) {
                         Some(map) => match map.tile(state.map.player.character.position.coords) {
                             Some(tile) => {
@@ -224,13 +269,30 @@ impl WorldWrapper {
                                 }
                             }
                         },
+                        // Stands in for a trade until NPC trades are scripted
+                        command::PartyCommand::Trade(index) => match trainer.party.get(index) {
+                            Some(pokemon) => {
+                                state.map.player.trade(index, &mut self.randoms.general);
+                                match evolution(
+                                    &self.manager.world.data.evolutions,
+                                    &pokemon.pokemon.id,
+                                    EvolutionTrigger::Trade,
+                                ) {
+                                    Some(into) => request = Some(WorldRequest::Evolve(index, into)),
+                                    None => {
+                                        info!("{} does not evolve by trading", pokemon.name())
+                                    }
+                                }
+                            }
+                            None => info!("No pokemon at index {}", index),
+                        },
                     },
                     WorldCommands::ClearBattle => state.map.player.battle.battling = None,
                     WorldCommands::GiveMove(id, index) => {
                         if let Some(m) = self.manager.world.movedex.try_get(&id) {
                             if let Some(pokemon) = trainer.party.get_mut(index) {
                                 pokemon.moves.push(OwnedMove::from(m.clone()));
-                                self.manager.update_capabilities(&mut state.map.player.character, trainer);
+                                self.manager.update_capabilities(&mut state.map.player, trainer);
                             } else {
                                 info!("No pokemon at index {}", index);
                             }
@@ -245,10 +307,14 @@ impl WorldWrapper {
                     WorldCommands::Capabilities => {
                         info!("Capabilities: {:?}", state.map.player.character.capabilities.iter());
                     },
+                    WorldCommands::Export(path) => request = Some(WorldRequest::Export(path)),
+                    WorldCommands::Import(path) => request = Some(WorldRequest::Import(path)),
                 },
                 Err(err) => self.commands.errors.borrow_mut().push(err),
             }
         }
+
+        request
     }
 
     pub fn draw(&self, gfx: &mut Graphics, state: &MapState) {
@@ -267,8 +333,10 @@ impl WorldWrapper {
         state: &mut MapState,
         trainer: &mut InitTrainer,
     ) -> Option<WorldRequest> {
-        self.manager.ui(app, plugins, egui, state);
-        self.menu.ui(app, plugins, egui, trainer)
+        self.manager
+            .ui(app, plugins, egui, state, &mut self.randoms, trainer);
+        self.menu
+            .ui(app, plugins, egui, &self.manager.world, state, trainer)
     }
 
     #[cfg(debug_assertions)]
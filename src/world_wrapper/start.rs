@@ -2,12 +2,28 @@ use std::{sync::Arc, rc::Rc};
 
 use firecore_battle_engine::pokengine::texture::{PokemonTextures, ItemTextures};
 
-use crate::{pokedex::trainer::InitTrainer, settings::Settings};
+use worldcli::{
+    gui::{BadgeCaseGui, TrainerCardGui},
+    worldlib::{
+        map::{
+            data::FieldType,
+            evolution::{evolution, EvolutionTrigger},
+            manager::WorldMapManager,
+        },
+        script::default::DefaultWorldScriptEngine,
+        state::map::MapState,
+    },
+};
 
 use crate::{
-    pokengine::{
-        gui::{bag::BagGui, party::PartyGui},
-    },
+    pokedex::{item::ItemId, moves::MoveId, trainer::InitTrainer},
+    settings::Settings,
+};
+
+use crate::pokengine::gui::{
+    bag::{BagAction, BagGui},
+    party::{PartyAction, PartyGui},
+    pokedex::{DexAreas, PokedexGui},
 };
 
 use crate::engine::{
@@ -22,16 +38,44 @@ pub struct StartMenu {
     settings: Rc<Settings>,
     party: PartyGui,
     bag: BagGui,
+    pokedex: PokedexGui,
+    badges: BadgeCaseGui,
+    card: TrainerCardGui,
+    picking: Option<Picking>,
+}
+
+/// Waiting on the player to pick something in another GUI.
+enum Picking {
+    /// A party member to use the item on
+    Use(ItemId),
+    /// A party member to give the item to
+    Give(ItemId),
+    /// An item for the party member to hold
+    Hold(usize),
+    /// Confirming the party member should swap its held item for this one
+    Swap(usize, ItemId),
 }
 
 impl StartMenu {
-    pub(crate) fn new(settings: Rc<Settings>, pokemon: Arc<PokemonTextures>, items: Arc<ItemTextures>) -> Self {
+    pub(crate) fn new(
+        settings: Rc<Settings>,
+        pokemon: Arc<PokemonTextures>,
+        items: Arc<ItemTextures>,
+        field: Vec<MoveId>,
+        areas: DexAreas,
+    ) -> Self {
         Self {
             alive: false,
             cursor: 0,
             settings,
-            party: PartyGui::new(pokemon),
-            bag: BagGui::new(items),
+            party: PartyGui::new(pokemon.clone())
+                .with_field_moves(field)
+                .with_held_items(),
+            bag: BagGui::new(items).with_held_items(),
+            pokedex: PokedexGui::new(pokemon, areas),
+            badges: Default::default(),
+            card: Default::default(),
+            picking: None,
         }
     }
 
@@ -40,15 +84,130 @@ impl StartMenu {
         app: &mut App,
         plugins: &mut Plugins,
         egui: &egui::Context,
+        world: &WorldMapManager<DefaultWorldScriptEngine>,
+        state: &mut MapState,
         user: &mut InitTrainer,
     ) -> Option<super::WorldRequest> {
         if pressed(app, plugins, Control::Start) {
             self.alive = !self.alive;
         }
         self.settings.ui(app, plugins, egui);
-        self.bag.ui(egui, &mut user.bag);
-        self.party.ui(egui, &mut user.party, app.timer.delta_f32());
-        match self.alive {
+        self.pokedex.ui(
+            app,
+            plugins,
+            egui,
+            &world.pokedex,
+            &state.player.dex.seen,
+            &state.player.dex.caught,
+        );
+        self.badges
+            .ui(app, plugins, egui, &world.data.badges, &state.player);
+        self.card.ui(
+            app,
+            plugins,
+            egui,
+            &state.player,
+            user.money,
+            &world.data.badges,
+        );
+        if let Some(action) = self.bag.ui(egui, &mut user.bag) {
+            self.bag.despawn();
+            match (self.picking.take(), action) {
+                (Some(Picking::Hold(pokemon)), BagAction::Use(id) | BagAction::Give(id)) => {
+                    self.give(world, state, user, pokemon, id)
+                }
+                (.., BagAction::Use(id)) => match world.data.targets_pokemon(&id) {
+                    true => {
+                        self.picking = Some(Picking::Use(id));
+                        self.party.spawn();
+                    }
+                    false => {
+                        if world.use_item(state, &id) {
+                            Self::take_item(user, &id);
+                        }
+                        self.alive = false;
+                    }
+                },
+                (.., BagAction::Give(id)) => {
+                    self.picking = Some(Picking::Give(id));
+                    self.party.spawn();
+                }
+            }
+        }
+        let mut request = None;
+        if let Some(action) = self.party.ui(egui, &mut user.party, app.timer.delta_f32()) {
+            match action {
+                PartyAction::Select(pokemon) => match self.picking.take() {
+                    Some(Picking::Use(id)) => {
+                        request = Self::use_item_on(world, state, user, pokemon, id);
+                        self.party.despawn();
+                        self.alive = false;
+                    }
+                    Some(Picking::Give(id)) => self.give(world, state, user, pokemon, id),
+                    picking => self.picking = picking,
+                },
+                PartyAction::Field(pokemon, id) => {
+                    world.use_move(state, user, pokemon, &id);
+                    self.party.despawn();
+                    self.alive = false;
+                }
+                PartyAction::Give(pokemon) => {
+                    self.picking = Some(Picking::Hold(pokemon));
+                    self.bag.spawn();
+                }
+                PartyAction::Take(pokemon) => {
+                    world.take_held_item(state, user, pokemon);
+                    self.party.despawn();
+                    self.alive = false;
+                }
+                PartyAction::Swap(a, b) => state.player.original_trainers.swap(a, b),
+            }
+        }
+        if let Some(Picking::Swap(pokemon, id)) = self.picking {
+            let mut answer = None;
+            egui::Window::new("Swap Items")
+                .title_bar(false)
+                .show(egui, |ui| {
+                    if let Some((pokemon, held)) = user
+                        .party
+                        .get(pokemon)
+                        .and_then(|p| p.item.as_ref().map(|held| (p, held)))
+                    {
+                        ui.label(format!(
+                            "{} is already holding {}.",
+                            pokemon.name(),
+                            held.name
+                        ));
+                    }
+                    ui.label("Would you like to switch the two items?");
+                    if ui.button("Yes").clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button("No").clicked() {
+                        answer = Some(false);
+                    }
+                });
+            if let Some(answer) = answer {
+                self.picking = None;
+                if answer {
+                    world.give_held_item(state, user, pokemon, &id);
+                    self.party.despawn();
+                    self.alive = false;
+                }
+            }
+        }
+        let town_map = user
+            .bag
+            .iter()
+            .find(|stack| {
+                stack.count != 0
+                    && matches!(
+                        world.data.items.get(&stack.item.id),
+                        Some(FieldType::TownMap)
+                    )
+            })
+            .map(|stack| stack.item.id);
+        let menu = match self.alive {
             true => egui::Window::new("Menu")
                 .title_bar(false)
                 .anchor(egui::Align2::RIGHT_TOP, [-5.0, 5.0])
@@ -59,12 +218,29 @@ impl StartMenu {
                     if ui.button("Settings").clicked() {
                         self.settings.spawn();
                     }
+                    if ui.button("Pokedex").clicked() {
+                        self.pokedex.spawn();
+                    }
+                    if ui.button("Trainer Card").clicked() {
+                        self.card.spawn();
+                    }
+                    if ui.button("Badges").clicked() {
+                        self.badges.spawn();
+                    }
                     if ui.button("Bag").clicked() {
+                        self.picking = None;
                         self.bag.spawn();
                     }
                     if ui.button("Party").clicked() {
+                        self.picking = None;
                         self.party.spawn();
                     }
+                    if let Some(id) = town_map {
+                        if ui.button("Town Map").clicked() {
+                            world.use_item(state, &id);
+                            self.alive = false;
+                        }
+                    }
                     if ui.button("Exit to Menu").clicked() {
                         return Some(super::WorldRequest::Exit);
                     }
@@ -84,7 +260,60 @@ impl StartMenu {
                 }),
         }
         .and_then(|i| i.inner)
-        .flatten()
+        .flatten();
+        request.or(menu)
+    }
+
+    /// Uses an item on a party member, evolving it if the item is an evolution stone.
+    fn use_item_on(
+        world: &WorldMapManager<DefaultWorldScriptEngine>,
+        state: &mut MapState,
+        user: &mut InitTrainer,
+        pokemon: usize,
+        id: ItemId,
+    ) -> Option<super::WorldRequest> {
+        match user.party.get(pokemon).and_then(|p| {
+            evolution(
+                &world.data.evolutions,
+                &p.pokemon.id,
+                EvolutionTrigger::Item(id),
+            )
+        }) {
+            Some(into) => {
+                Self::take_item(user, &id);
+                Some(super::WorldRequest::Evolve(pokemon, into))
+            }
+            None => {
+                if world.use_item_on(state, user, pokemon, &id) {
+                    Self::take_item(user, &id);
+                }
+                None
+            }
+        }
+    }
+
+    /// Gives an item to a party member, asking first if it would replace a held item.
+    fn give(
+        &mut self,
+        world: &WorldMapManager<DefaultWorldScriptEngine>,
+        state: &mut MapState,
+        user: &mut InitTrainer,
+        pokemon: usize,
+        id: ItemId,
+    ) {
+        match user.party.get(pokemon).map(|p| p.item.is_some()) {
+            Some(true) => self.picking = Some(Picking::Swap(pokemon, id)),
+            _ => {
+                world.give_held_item(state, user, pokemon, &id);
+                self.party.despawn();
+                self.alive = false;
+            }
+        }
+    }
+
+    /// Uses up one of the item, dropping its stack from the bag once it is empty
+    fn take_item(user: &mut InitTrainer, id: &ItemId) {
+        user.bag.try_take(id, 1);
     }
 
     pub fn spawn(&mut self) {